@@ -1,5 +1,5 @@
 use super::DayResult;
-use crate::utils::bench::time_execution;
+use crate::utils::{bench::time_execution, scan};
 use std::{collections::HashMap, fs};
 
 pub fn run() -> DayResult {
@@ -23,16 +23,13 @@ fn parse(input: &str) -> (Vec<u64>, Vec<u64>, HashMap<u64, u64>) {
     let mut right_map = HashMap::new();
 
     for line in input.lines() {
-        let (left, right) = line
-            .split_once("   ")
-            .expect("Exactly two numbers should be present on each line separated by three spaces");
-
-        let left = left
-            .parse()
-            .expect("Left half should be a positive integer");
-        let right = right
-            .parse()
-            .expect("Right half should be a positive integer");
+        let mut numbers = scan::separated::<u64>(line.as_bytes(), b"   ").map(|n| {
+            n.expect("Each line should contain positive integers separated by three spaces")
+        });
+        let (Some(left), Some(right), None) = (numbers.next(), numbers.next(), numbers.next())
+        else {
+            panic!("Exactly two numbers should be present on each line");
+        };
 
         left_list.push(left);
         right_list.push(right);
@@ -68,7 +65,7 @@ fn part2(left_list: &[u64], right_map: &HashMap<u64, u64>) -> String {
 mod tests {
     use super::*;
 
-    const INPUT: &'static str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3";
+    const INPUT: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3";
 
     #[test]
     fn test_parse() {
@@ -1,7 +1,7 @@
 use super::DayResult;
-use crate::utils::bench::time_execution;
+use crate::utils::{bench::time_execution, scan};
 use itertools::Itertools;
-use std::fs;
+use std::{fs, io};
 
 pub fn run() -> DayResult {
     let input = fs::read_to_string("inputs/02.in").expect("Input file should be readable");
@@ -19,51 +19,148 @@ pub fn run() -> DayResult {
 }
 
 fn parse(input: &str) -> Vec<Vec<u64>> {
-    let lines = input.lines();
-    lines
+    input
+        .lines()
         .map(|line| {
-            line.split(' ')
-                .map(|level| {
-                    level
-                        .parse()
-                        .expect("Input should only contain positive integers")
-                })
-                .collect()
+            scan::separated(line.as_bytes(), b" ")
+                .collect::<Result<_, _>>()
+                .expect("Input should only contain positive integers separated by spaces")
         })
         .collect()
 }
 
-fn is_safe(report: &[u64]) -> bool {
-    let is_monotonic =
-        report.is_sorted() || report.is_sorted_by(|level_a, level_b| level_a >= level_b);
-    let is_gradual = report
-        .windows(2)
-        .all(|w| (1..=3).contains(&w[1].abs_diff(w[0])));
-    is_monotonic && is_gradual
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Tolerance {
+    max_removals: usize,
+    min_step: u64,
+    max_step: u64,
+}
+
+const STRICT: Tolerance = Tolerance {
+    max_removals: 0,
+    min_step: 1,
+    max_step: 3,
+};
+
+const DAMPENED: Tolerance = Tolerance {
+    max_removals: 1,
+    ..STRICT
+};
+
+fn removals(report: &[u64], tolerance: &Tolerance, increasing: bool) -> Option<Vec<usize>> {
+    let n = report.len();
+    let k = tolerance.max_removals;
+    let is_step = |from: u64, to: u64| {
+        (if increasing { to >= from } else { to <= from })
+            && (tolerance.min_step..=tolerance.max_step).contains(&from.abs_diff(to))
+    };
+
+    let mut cost = vec![usize::MAX; n];
+    let mut parent = vec![None; n];
+    for i in 0..n {
+        if i <= k {
+            cost[i] = i;
+        }
+        for j in i.saturating_sub(k + 1)..i {
+            if cost[j] != usize::MAX && is_step(report[j], report[i]) {
+                let dropped = cost[j] + (i - j - 1);
+                if dropped < cost[i] {
+                    cost[i] = dropped;
+                    parent[i] = Some(j);
+                }
+            }
+        }
+    }
+
+    let last = (n.saturating_sub(k + 1)..n)
+        .filter(|&i| cost[i] != usize::MAX)
+        .min_by_key(|&i| cost[i] + (n - 1 - i))
+        .filter(|&i| cost[i] + (n - 1 - i) <= k);
+    let Some(last) = last else {
+        return (n == 0).then(Vec::new);
+    };
+
+    let mut kept = vec![false; n];
+    let mut current = Some(last);
+    while let Some(i) = current {
+        kept[i] = true;
+        current = parent[i];
+    }
+    Some((0..n).filter(|&i| !kept[i]).collect())
+}
+
+fn dampen(report: &[u64], tolerance: &Tolerance) -> Option<Vec<usize>> {
+    let increasing = removals(report, tolerance, true);
+    let decreasing = removals(report, tolerance, false);
+    match (increasing, decreasing) {
+        (Some(a), Some(b)) => Some(if b.len() < a.len() { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
+fn count_safe(reports: &[Vec<u64>], tolerance: &Tolerance) -> usize {
+    reports
+        .iter()
+        .filter(|report| dampen(report, tolerance).is_some())
+        .count()
 }
 
 fn part1(reports: &[Vec<u64>]) -> String {
-    let safe_report_count = reports.iter().filter(|report| is_safe(report)).count();
+    let safe_report_count = count_safe(reports, &STRICT);
     format!("{safe_report_count}")
 }
 
 fn part2(reports: Vec<Vec<u64>>) -> String {
-    let safe_report_count = reports
-        .into_iter()
-        .map(|report| {
-            let k = report.len() - 1;
-            report.into_iter().combinations(k).any(|r| is_safe(&r))
-        })
-        .filter(|&is_safe| is_safe)
-        .count();
+    let safe_report_count = count_safe(&reports, &DAMPENED);
     format!("{safe_report_count}")
 }
 
+pub fn explain(
+    max_removals: Option<usize>,
+    min_step: Option<u64>,
+    max_step: Option<u64>,
+) -> io::Result<()> {
+    let input = fs::read_to_string("inputs/02.in")?;
+    let reports = parse(&input);
+    let tolerance = Tolerance {
+        max_removals: max_removals.unwrap_or(DAMPENED.max_removals),
+        min_step: min_step.unwrap_or(DAMPENED.min_step),
+        max_step: max_step.unwrap_or(DAMPENED.max_step),
+    };
+
+    for (i, report) in reports.iter().enumerate() {
+        let verdict = match dampen(report, &tolerance) {
+            None => "unsafe".to_string(),
+            Some(dropped) if dropped.is_empty() => "safe".to_string(),
+            Some(dropped) => format!(
+                "safe after dropping {}",
+                dropped
+                    .iter()
+                    .map(|&level| format!("level {} ({})", level + 1, report[level]))
+                    .join(", ")
+            ),
+        };
+        println!(
+            "Report {:>4}: {:<32} {verdict}",
+            i + 1,
+            report.iter().join(" ")
+        );
+    }
+    println!(
+        "\n{} of {} reports are safe",
+        count_safe(&reports, &tolerance),
+        reports.len()
+    );
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::rng::Rng;
 
-    const INPUT: &'static str = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9";
+    const INPUT: &str = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9";
 
     #[test]
     fn test_parse() {
@@ -77,6 +174,85 @@ mod tests {
         assert_eq!(reports[5], vec![1, 3, 6, 7, 9]);
     }
 
+    fn brute_force(report: &[u64], tolerance: &Tolerance) -> bool {
+        let is_safe = |report: &[u64]| {
+            let is_monotonic =
+                report.is_sorted() || report.is_sorted_by(|level_a, level_b| level_a >= level_b);
+            let is_gradual = report
+                .windows(2)
+                .all(|w| (tolerance.min_step..=tolerance.max_step).contains(&w[1].abs_diff(w[0])));
+            is_monotonic && is_gradual
+        };
+        (0..=tolerance.max_removals.min(report.len())).any(|removed| {
+            report
+                .iter()
+                .copied()
+                .combinations(report.len() - removed)
+                .any(|kept| is_safe(&kept))
+        })
+    }
+
+    #[test]
+    fn test_dampen() {
+        let reports = parse(INPUT);
+        let dropped: Vec<_> = reports
+            .iter()
+            .map(|report| dampen(report, &DAMPENED))
+            .collect();
+        assert_eq!(
+            dropped,
+            vec![
+                Some(vec![]),
+                None,
+                None,
+                Some(vec![2]),
+                Some(vec![3]),
+                Some(vec![])
+            ]
+        );
+        let lenient = Tolerance {
+            max_removals: 2,
+            min_step: 1,
+            max_step: 5,
+        };
+        assert_eq!(dampen(&reports[1], &lenient), Some(vec![]));
+        assert_eq!(dampen(&[1, 9, 2, 8, 3], &lenient), Some(vec![1, 3]));
+    }
+
+    #[test]
+    fn test_dampen_matches_brute_force() {
+        let mut rng = Rng::new(2024);
+        for _ in 0..3000 {
+            let len = rng.below(9) as usize;
+            let report: Vec<u64> = (0..len).map(|_| rng.below(12)).collect();
+            let tolerance = Tolerance {
+                max_removals: rng.below(4) as usize,
+                min_step: rng.below(2),
+                max_step: 1 + rng.below(4),
+            };
+            let dropped = dampen(&report, &tolerance);
+            assert_eq!(
+                dropped.is_some(),
+                brute_force(&report, &tolerance),
+                "{report:?} {tolerance:?}"
+            );
+            if let Some(dropped) = dropped {
+                assert!(dropped.len() <= tolerance.max_removals);
+                let kept: Vec<_> = (0..report.len())
+                    .filter(|i| !dropped.contains(i))
+                    .map(|i| report[i])
+                    .collect();
+                assert!(brute_force(
+                    &kept,
+                    &Tolerance {
+                        max_removals: 0,
+                        ..tolerance
+                    }
+                ));
+            }
+        }
+    }
+
     #[test]
     fn test_part1() {
         let reports = parse(INPUT);
@@ -1,7 +1,7 @@
 use super::DayResult;
 use crate::utils::bench::time_execution;
-use regex::{Match, Regex};
-use std::fs;
+use itertools::Itertools;
+use std::{fmt, fs, io, ops::Range};
 
 pub fn run() -> DayResult {
     let input = fs::read_to_string("inputs/03.in").expect("Input file should be readable");
@@ -18,72 +18,227 @@ pub fn run() -> DayResult {
     }
 }
 
-fn parse(input: &str) -> Vec<Match> {
-    Regex::new(r"mul\([0-9]+,[0-9]+\)|do\(\)|don't\(\)")
-        .unwrap()
-        .find_iter(input)
-        .collect()
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Mul,
+    Do,
+    Dont,
 }
 
-fn mul(instruction: &str) -> u64 {
-    let (a, b) = instruction[4..instruction.len() - 1]
-        .split_once(',')
-        .unwrap();
-    let (a, b) = (
-        a.parse::<u64>()
-            .expect("Input should only contain positive integers"),
-        b.parse::<u64>()
-            .expect("Input should only contain positive integers"),
-    );
-    a * b
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Signature {
+    name: &'static str,
+    op: Op,
+    arity: usize,
 }
 
-fn part1(instructions: &[Match]) -> String {
-    let ans: u64 = instructions
-        .iter()
-        .map(|instruction| {
-            let instruction = instruction.as_str();
-            if instruction.len() > 7 {
-                mul(instruction)
-            } else {
-                0
+const SIGNATURES: [Signature; 3] = [
+    Signature {
+        name: "mul",
+        op: Op::Mul,
+        arity: 2,
+    },
+    Signature {
+        name: "do",
+        op: Op::Do,
+        arity: 0,
+    },
+    Signature {
+        name: "don't",
+        op: Op::Dont,
+        arity: 0,
+    },
+];
+
+const MAX_DIGITS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Instruction {
+    op: Op,
+    name: &'static str,
+    args: Vec<u64>,
+    span: Range<usize>,
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.name, self.args.iter().join(","))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Instruction(Instruction),
+    Ignored(Range<usize>),
+}
+
+struct Tokenizer<'a> {
+    input: &'a [u8],
+    signatures: &'a [Signature],
+    max_digits: usize,
+    pos: usize,
+    pending: Option<Instruction>,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(input: &'a str, signatures: &'a [Signature]) -> Self {
+        Self {
+            input: input.as_bytes(),
+            signatures,
+            max_digits: MAX_DIGITS,
+            pos: 0,
+            pending: None,
+        }
+    }
+
+    fn with_max_digits(mut self, max_digits: usize) -> Self {
+        self.max_digits = max_digits;
+        self
+    }
+
+    fn argument_at(&self, pos: usize) -> Option<(u64, usize)> {
+        let digits = self.input[pos..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if !(1..=self.max_digits).contains(&digits) {
+            return None;
+        }
+        let value = self.input[pos..pos + digits]
+            .iter()
+            .try_fold(0_u64, |value, b| {
+                value.checked_mul(10)?.checked_add(u64::from(b - b'0'))
+            })?;
+        Some((value, pos + digits))
+    }
+
+    fn instruction_at(&self, start: usize) -> Option<Instruction> {
+        self.signatures.iter().find_map(|signature| {
+            let mut pos = start + signature.name.len();
+            if !self.input[start..].starts_with(signature.name.as_bytes())
+                || self.input.get(pos) != Some(&b'(')
+            {
+                return None;
+            }
+            pos += 1;
+
+            let mut args = Vec::with_capacity(signature.arity);
+            for i in 0..signature.arity {
+                if i > 0 {
+                    (self.input.get(pos) == Some(&b',')).then_some(())?;
+                    pos += 1;
+                }
+                let (value, end) = self.argument_at(pos)?;
+                args.push(value);
+                pos = end;
             }
+
+            (self.input.get(pos) == Some(&b')')).then(|| Instruction {
+                op: signature.op,
+                name: signature.name,
+                args,
+                span: start..pos + 1,
+            })
         })
-        .sum();
-    format!("{ans}")
+    }
 }
 
-fn part2(instructions: &[Match]) -> String {
-    let mut enabled = true;
+impl Iterator for Tokenizer<'_> {
+    type Item = Token;
 
-    let ans: u64 = instructions
-        .iter()
-        .map(|instruction| {
-            let instruction = instruction.as_str();
-            match instruction.len() {
-                4 => {
-                    enabled = true;
-                    0
-                }
-                7 => {
-                    enabled = false;
-                    0
+    fn next(&mut self) -> Option<Token> {
+        if let Some(instruction) = self.pending.take() {
+            self.pos = instruction.span.end;
+            return Some(Token::Instruction(instruction));
+        }
+
+        let start = self.pos;
+        while self.pos < self.input.len() {
+            if let Some(instruction) = self.instruction_at(self.pos) {
+                if self.pos == start {
+                    self.pos = instruction.span.end;
+                    return Some(Token::Instruction(instruction));
                 }
-                _ if enabled => mul(instruction),
-                _ => 0,
+                self.pending = Some(instruction);
+                return Some(Token::Ignored(start..self.pos));
             }
+            self.pos += 1;
+        }
+        (start < self.pos).then_some(Token::Ignored(start..self.pos))
+    }
+}
+
+fn parse(input: &str) -> Vec<Instruction> {
+    Tokenizer::new(input, &SIGNATURES)
+        .filter_map(|token| match token {
+            Token::Instruction(instruction) => Some(instruction),
+            Token::Ignored(_) => None,
         })
-        .sum();
+        .collect()
+}
+
+fn execute(instructions: &[Instruction], conditionals: bool) -> u64 {
+    let mut enabled = true;
+    instructions
+        .iter()
+        .map(|instruction| match instruction.op {
+            Op::Do => {
+                enabled = true;
+                0
+            }
+            Op::Dont => {
+                enabled = !conditionals;
+                0
+            }
+            Op::Mul if enabled => instruction.args.iter().product(),
+            Op::Mul => 0,
+        })
+        .sum()
+}
+
+fn part1(instructions: &[Instruction]) -> String {
+    let ans = execute(instructions, false);
     format!("{ans}")
 }
 
+fn part2(instructions: &[Instruction]) -> String {
+    let enabled_result = execute(instructions, true);
+    format!("{enabled_result}")
+}
+
+pub fn report(max_digits: Option<usize>) -> io::Result<()> {
+    let input = fs::read_to_string("inputs/03.in")?;
+    let (mut instruction_count, mut ignored_bytes) = (0, 0);
+
+    let tokens =
+        Tokenizer::new(&input, &SIGNATURES).with_max_digits(max_digits.unwrap_or(MAX_DIGITS));
+    for token in tokens {
+        match token {
+            Token::Instruction(instruction) => {
+                instruction_count += 1;
+                println!(
+                    "{:>6}..{:<6} {instruction}",
+                    instruction.span.start, instruction.span.end
+                );
+            }
+            Token::Ignored(span) => {
+                ignored_bytes += span.len();
+                let text = String::from_utf8_lossy(&input.as_bytes()[span.clone()]);
+                println!("{:>6}..{:<6} ignored {text:?}", span.start, span.end);
+            }
+        }
+    }
+    println!("\n{instruction_count} instructions, {ignored_bytes} bytes ignored as corruption");
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT1: &'static str =
-        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-    const INPUT2: &'static str =
+    const INPUT1: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+    const INPUT2: &str =
         "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
 
     #[test]
@@ -91,12 +246,42 @@ mod tests {
         let matches = parse(INPUT2);
 
         assert_eq!(matches.len(), 6);
-        assert_eq!(matches[0].as_str(), "mul(2,4)");
-        assert_eq!(matches[1].as_str(), "don't()");
-        assert_eq!(matches[2].as_str(), "mul(5,5)");
-        assert_eq!(matches[3].as_str(), "mul(11,8)");
-        assert_eq!(matches[4].as_str(), "do()");
-        assert_eq!(matches[5].as_str(), "mul(8,5)");
+        assert_eq!(matches[0].to_string(), "mul(2,4)");
+        assert_eq!(matches[1].to_string(), "don't()");
+        assert_eq!(matches[2].to_string(), "mul(5,5)");
+        assert_eq!(matches[3].to_string(), "mul(11,8)");
+        assert_eq!(matches[4].to_string(), "do()");
+        assert_eq!(matches[5].to_string(), "mul(8,5)");
+        assert_eq!(matches[0].span, 1..9);
+        assert_eq!(matches[5].span, 64..72);
+        assert_eq!(matches[5].args, vec![8, 5]);
+    }
+
+    #[test]
+    fn test_tokenizer_spans() {
+        let tokens: Vec<_> = Tokenizer::new("xmul(2,4)%do()mul(1,2", &SIGNATURES).collect();
+        assert_eq!(tokens.len(), 5);
+        assert_eq!(tokens[0], Token::Ignored(0..1));
+        assert!(matches!(&tokens[1], Token::Instruction(i) if i.span == (1..9)));
+        assert_eq!(tokens[2], Token::Ignored(9..10));
+        assert!(matches!(&tokens[3], Token::Instruction(i) if i.op == Op::Do));
+        assert_eq!(tokens[4], Token::Ignored(14..21));
+    }
+
+    #[test]
+    fn test_digit_limit() {
+        let input = "mul(123,4)mul(1234,5)mul(,1)mul(1,2,3)don't(1)";
+        let ops = |max_digits| {
+            Tokenizer::new(input, &SIGNATURES)
+                .with_max_digits(max_digits)
+                .filter_map(|token| match token {
+                    Token::Instruction(instruction) => Some(instruction.to_string()),
+                    Token::Ignored(_) => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ops(3), vec!["mul(123,4)"]);
+        assert_eq!(ops(4), vec!["mul(123,4)", "mul(1234,5)"]);
     }
 
     #[test]
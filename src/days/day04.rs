@@ -1,5 +1,8 @@
 use super::DayResult;
-use crate::utils::bench::time_execution;
+use crate::utils::{
+    bench::time_execution,
+    grid::{Direction, Grid, Stencil, WordSearch},
+};
 use std::fs;
 
 pub fn run() -> DayResult {
@@ -17,119 +20,146 @@ pub fn run() -> DayResult {
     }
 }
 
-fn parse(input: &str) -> Vec<Vec<u8>> {
-    input.lines().map(|line| line.bytes().collect()).collect()
+fn parse(input: &str) -> Grid<u8> {
+    Grid::from(input)
 }
 
-fn match_count(
-    word_search: &[Vec<u8>],
-    target: &[u8],
-    r: usize,
-    c: usize,
-    m: usize,
-    n: usize,
-) -> u8 {
-    let (m, n, r, c) = (m as i64, n as i64, r as i64, c as i64);
-    let mut count = 0;
-
-    for i in -1..=1 {
-        for j in -1..=1 {
-            if target.iter().enumerate().all(|(k, &ch)| {
-                let nr = r + k as i64 * i;
-                let nc = c + k as i64 * j;
-                (0..m).contains(&nr)
-                    && (0..n).contains(&nc)
-                    && word_search[nr as usize][nc as usize] == ch
-            }) {
-                count += 1;
-            }
-        }
-    }
-
-    count
-}
-
-fn part1(word_search: &[Vec<u8>], target: &[u8]) -> String {
-    let first_char = target[0];
-    let (m, n) = (word_search.len(), word_search[0].len());
-    let mut xmas_count = 0;
-
-    for r in 0..m {
-        for c in 0..n {
-            if word_search[r][c] == first_char {
-                xmas_count += u64::from(match_count(word_search, target, r, c, m, n));
-            }
-        }
-    }
-
+fn part1(word_search: &Grid<u8>, target: &[u8]) -> String {
+    let xmas_count = WordSearch::new(&[target])
+        .find(word_search, &Direction::ALL)
+        .len();
     format!("{xmas_count}")
 }
 
-fn is_x(
-    word_search: &[Vec<u8>],
-    target: &[u8],
-    rev_target: &[u8],
-    r: usize,
-    c: usize,
-    th: usize,
-) -> bool {
-    let (r, c, th) = (r as isize, c as isize, th as isize);
-
-    let check_diagonal = |dir: isize, target: &[u8]| {
-        (-th..=th)
-            .enumerate()
-            .all(|(i, k)| word_search[(r + k) as usize][(c + k * dir) as usize] == target[i])
-    };
-
-    (check_diagonal(1, target) || check_diagonal(1, rev_target))
-        && (check_diagonal(-1, target) || check_diagonal(-1, rev_target))
-}
-
-fn part2(word_search: &[Vec<u8>], target: &[u8]) -> String {
-    let t = target.len();
-    if t % 2 == 0 {
-        eprintln!("The target must be of odd length");
-        return "-".into();
-    }
-    let th = t / 2;
-    let middle_char = target[th];
-    let rev_target: Vec<u8> = target.iter().rev().copied().collect();
-    let (m, n) = (word_search.len(), word_search[0].len());
-    let mut x_mas_count: usize = 0;
-
-    for r in th..m - th {
-        for c in th..n - th {
-            if word_search[r][c] == middle_char && is_x(word_search, target, &rev_target, r, c, th)
-            {
-                x_mas_count += 1;
-            }
-        }
-    }
-
+fn part2(word_search: &Grid<u8>, target: &[u8]) -> String {
+    let x_mas_count = Stencil::cross(target).find(word_search).len();
     format!("{x_mas_count}")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::grid::{Coord, Orientation, StencilMatch, WordMatch};
 
-    const INPUT: &'static str = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\nXMASAMXAMM\nXXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX";
+    const INPUT: &str = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\nXMASAMXAMM\nXXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX";
 
     #[test]
     fn test_parse() {
         let grid = parse(INPUT);
-        assert_eq!(grid.len(), 10);
-        assert_eq!(grid[0].len(), 10);
-        assert_eq!(grid[0], vec![77, 77, 77, 83, 88, 88, 77, 65, 83, 77]);
-        assert_eq!(grid[1], vec![77, 83, 65, 77, 88, 77, 83, 77, 83, 65]);
-        assert_eq!(grid[2], vec![65, 77, 88, 83, 88, 77, 65, 65, 77, 77]);
-        assert_eq!(grid[3], vec![77, 83, 65, 77, 65, 83, 77, 83, 77, 88]);
-        assert_eq!(grid[4], vec![88, 77, 65, 83, 65, 77, 88, 65, 77, 77]);
-        assert_eq!(grid[5], vec![88, 88, 65, 77, 77, 88, 88, 65, 77, 65]);
-        assert_eq!(grid[6], vec![83, 77, 83, 77, 83, 65, 83, 88, 83, 83]);
-        assert_eq!(grid[7], vec![83, 65, 88, 65, 77, 65, 83, 65, 65, 65]);
-        assert_eq!(grid[8], vec![77, 65, 77, 77, 77, 88, 77, 77, 77, 77]);
-        assert_eq!(grid[9], vec![77, 88, 77, 88, 65, 88, 77, 65, 83, 88]);
+        assert_eq!(grid.height(), 10);
+        assert_eq!(grid.width(), 10);
+        assert_eq!(grid.row(0), [77, 77, 77, 83, 88, 88, 77, 65, 83, 77]);
+        assert_eq!(grid.row(1), [77, 83, 65, 77, 88, 77, 83, 77, 83, 65]);
+        assert_eq!(grid.row(2), [65, 77, 88, 83, 88, 77, 65, 65, 77, 77]);
+        assert_eq!(grid.row(3), [77, 83, 65, 77, 65, 83, 77, 83, 77, 88]);
+        assert_eq!(grid.row(4), [88, 77, 65, 83, 65, 77, 88, 65, 77, 77]);
+        assert_eq!(grid.row(5), [88, 88, 65, 77, 77, 88, 88, 65, 77, 65]);
+        assert_eq!(grid.row(6), [83, 77, 83, 77, 83, 65, 83, 88, 83, 83]);
+        assert_eq!(grid.row(7), [83, 65, 88, 65, 77, 65, 83, 65, 65, 65]);
+        assert_eq!(grid.row(8), [77, 65, 77, 77, 77, 88, 77, 77, 77, 77]);
+        assert_eq!(grid.row(9), [77, 88, 77, 88, 65, 88, 77, 65, 83, 88]);
+    }
+
+    #[test]
+    fn test_word_search() {
+        let grid = Grid::from("SHEX\nAHIS\nXSHE");
+        let words = WordSearch::new(&["HE", "SHE", "HIS", "HERS"]);
+        let mut matches = words.find(&grid, &[Direction::East, Direction::SouthEast]);
+        matches.sort_by_key(|m| (m.start.r, m.start.c, m.word));
+        assert_eq!(
+            matches,
+            vec![
+                WordMatch {
+                    word: 1,
+                    start: Coord::new(0, 0),
+                    direction: Direction::East
+                },
+                WordMatch {
+                    word: 0,
+                    start: Coord::new(0, 1),
+                    direction: Direction::East
+                },
+                WordMatch {
+                    word: 2,
+                    start: Coord::new(1, 1),
+                    direction: Direction::East
+                },
+                WordMatch {
+                    word: 1,
+                    start: Coord::new(2, 1),
+                    direction: Direction::East
+                },
+                WordMatch {
+                    word: 0,
+                    start: Coord::new(2, 2),
+                    direction: Direction::East
+                },
+            ]
+        );
+        assert_eq!(words.word(2), b"HIS");
+
+        let grid = parse(INPUT);
+        let xmas = WordSearch::new(&["XMAS", "SAMX"]).find(&grid, &Direction::ALL);
+        assert_eq!(xmas.len(), 36);
+        assert!(xmas.contains(&WordMatch {
+            word: 0,
+            start: Coord::new(4, 6),
+            direction: Direction::West
+        }));
+    }
+
+    #[test]
+    fn test_stencil() {
+        let grid = parse(INPUT);
+        let cross = Stencil::cross(b"MAS");
+        assert_eq!(cross, Stencil::parse("M?M\n?A?\nS?S"));
+        assert_eq!(cross.orientations().len(), 4);
+        assert_eq!(
+            Stencil::parse("M?S\n?A?\nM?S").find(&grid).len(),
+            9,
+            "every orientation of the same stencil"
+        );
+        assert!(cross.find(&grid).contains(&StencilMatch {
+            origin: Coord::new(0, 1),
+            orientation: Orientation {
+                rotation: 270,
+                reflected: false
+            }
+        }));
+
+        let plus = Stencil::plus(b"MAS");
+        assert_eq!(plus, Stencil::parse("?M?\nMAS\n?S?"));
+        assert_eq!(plus.orientations().len(), 4);
+        assert_eq!(Stencil::parse("XM").orientations().len(), 4);
+        assert_eq!(Stencil::parse("X").orientations().len(), 1);
+        let corner = Stencil::parse("X?\nMA");
+        assert_eq!(corner.orientations().len(), 8);
+        let found = Stencil::parse("XM\nM?").find(&grid);
+        assert!(found.iter().all(|m| {
+            Stencil::parse("XM\nM?")
+                .oriented(m.orientation)
+                .matches_at(&grid, m.origin)
+        }));
+    }
+
+    #[test]
+    fn test_stencil_wildcards() {
+        let grid = Grid::from("#.#\n.#.\n#.#");
+        assert_eq!(Stencil::parse("#.#").find(&grid).len(), 4);
+        assert_eq!(Stencil::parse("#?#").find(&grid).len(), 4);
+        assert_eq!(Stencil::parse("#?#\n?#?").find(&grid).len(), 4);
+        assert_eq!(Stencil::parse("#.\n.#").find(&grid).len(), 4);
+        assert_eq!(Stencil::parse("...").find(&grid).len(), 0);
+        assert_eq!(
+            Stencil::parse_with("M.M\n.A.\nS.S", b'.'),
+            Stencil::cross(b"MAS")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Cross word should have odd length")]
+    fn test_cross_even_length() {
+        Stencil::cross(b"MASS");
     }
 
     #[test]
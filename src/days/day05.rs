@@ -1,6 +1,10 @@
 use super::DayResult;
-use crate::utils::bench::time_execution;
-use std::{cmp::Ordering, collections::HashSet, fs};
+use crate::utils::{
+    bench::time_execution,
+    parse::{normalise, ParseResult, Span},
+};
+use itertools::Itertools;
+use std::{collections::HashSet, fmt, fs};
 
 pub fn run() -> DayResult {
     let input = fs::read_to_string("inputs/05.in").expect("Input file should be readable");
@@ -18,70 +22,132 @@ pub fn run() -> DayResult {
 }
 
 fn parse(input: &str) -> (HashSet<(u8, u8)>, Vec<Vec<u8>>) {
-    let input = input.replace('\r', "");
-    let (rules, updates) = input
-        .split_once("\n\n")
+    let input = normalise(input);
+    let [rules, updates] = Span::new(&input)
+        .sections_array()
         .expect("Rules and updates should be separated by a line");
     let rules = rules
         .lines()
         .map(|rule| {
-            let (a, b) = rule
-                .split_once('|')
-                .expect("Rules should be pipe delimited");
-            (
-                a.parse::<u8>()
-                    .expect("Page numbers should be positive integers"),
-                b.parse::<u8>()
-                    .expect("Page numbers should be positive integers"),
-            )
+            let (a, b) = rule.split_once("|")?;
+            Ok((a.parse()?, b.parse()?))
         })
-        .collect();
+        .collect::<ParseResult<_>>()
+        .expect("Rules should be pipe delimited positive integers");
     let updates = updates
         .lines()
-        .map(|rule| {
-            rule.split(',')
-                .map(|page| {
-                    page.parse::<u8>()
-                        .expect("Page numbers should be positive integers")
-                })
-                .collect()
-        })
-        .collect();
+        .map(|update| update.list(",", |page| page.parse()))
+        .collect::<ParseResult<_>>()
+        .expect("Updates should be comma separated positive integers");
     (rules, updates)
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OrderError {
+    Cycle(Vec<u8>),
+    Ambiguous(u8, u8),
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderError::Cycle(pages) => {
+                write!(f, "rules form a cycle {}", pages.iter().join(" -> "))
+            }
+            OrderError::Ambiguous(a, b) => write!(f, "no rule orders pages {a} and {b}"),
+        }
+    }
+}
+
+fn is_ordered(rules: &HashSet<(u8, u8)>, update: &[u8]) -> bool {
+    update
+        .iter()
+        .tuple_combinations()
+        .all(|(&a, &b)| !rules.contains(&(b, a)))
+}
+
+fn topological_order(rules: &HashSet<(u8, u8)>, update: &[u8]) -> Result<Vec<u8>, OrderError> {
+    let n = update.len();
+    let mut successors = vec![Vec::new(); n];
+    let mut predecessors = vec![Vec::new(); n];
+    let mut in_degree = vec![0; n];
+    for (i, j) in (0..n).cartesian_product(0..n) {
+        if rules.contains(&(update[i], update[j])) {
+            successors[i].push(j);
+            predecessors[j].push(i);
+            in_degree[j] += 1;
+        }
+    }
+
+    let mut ready: Vec<_> = (0..n).filter(|&i| in_degree[i] == 0).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(i) = ready.pop() {
+        if let Some(&j) = ready.last() {
+            return Err(OrderError::Ambiguous(update[j], update[i]));
+        }
+        order.push(update[i]);
+        for &j in &successors[i] {
+            in_degree[j] -= 1;
+            if in_degree[j] == 0 {
+                ready.push(j);
+            }
+        }
+    }
+
+    if order.len() == n {
+        return Ok(order);
+    }
+    let mut walk = vec![(0..n)
+        .find(|&i| in_degree[i] > 0)
+        .expect("Unordered pages should remain")];
+    loop {
+        let current = walk[walk.len() - 1];
+        let next = predecessors[current]
+            .iter()
+            .copied()
+            .find(|&i| in_degree[i] > 0)
+            .expect("Every remaining page should have a remaining predecessor");
+        if let Some(start) = walk.iter().position(|&i| i == next) {
+            let cycle = walk[start..]
+                .iter()
+                .chain([&next])
+                .rev()
+                .map(|&i| update[i]);
+            return Err(OrderError::Cycle(cycle.collect()));
+        }
+        walk.push(next);
+    }
+}
+
 fn part1(rules: &HashSet<(u8, u8)>, updates: &[Vec<u8>]) -> String {
     let correct_order_total: u64 = updates
         .iter()
-        .filter(|update| update.is_sorted_by(|&a, &b| rules.contains(&(a, b))))
+        .filter(|update| is_ordered(rules, update))
         .map(|update| u64::from(update[update.len() / 2]))
         .sum();
     format!("{correct_order_total}")
 }
 
 fn part2(rules: &HashSet<(u8, u8)>, updates: Vec<Vec<u8>>) -> String {
-    let incorrect_order_total: u64 = updates
+    let incorrect_order_total: Result<u64, OrderError> = updates
         .into_iter()
-        .filter(|update| !update.is_sorted_by(|&a, &b| rules.contains(&(a, b))))
-        .map(|mut update| {
-            update.sort_by(|&a, &b| {
-                if rules.contains(&(a, b)) {
-                    Ordering::Less
-                } else {
-                    Ordering::Greater
-                }
-            });
-            u64::from(update[update.len() / 2])
+        .filter(|update| !is_ordered(rules, update))
+        .map(|update| {
+            let order = topological_order(rules, &update)?;
+            Ok(u64::from(order[order.len() / 2]))
         })
         .sum();
-    format!("{incorrect_order_total}")
+    match incorrect_order_total {
+        Ok(total) => format!("{total}"),
+        Err(err) => format!("error: {err}"),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &'static str = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\r\n\r\n75,47,61,53,29\n97,61,53,29,13\n75,29,13\n75,97,47,61,53\n61,13,29\n97,13,75,29,47";
+    const INPUT: &str = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\r\n\r\n75,47,61,53,29\n97,61,53,29,13\n75,29,13\n75,97,47,61,53\n61,13,29\n97,13,75,29,47";
 
     #[test]
     fn test_parse() {
@@ -122,6 +188,45 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_parse_crlf() {
+        assert_eq!(parse(&INPUT.replace('\n', "\r\n")), parse(INPUT));
+    }
+
+    #[test]
+    fn test_topological_order() {
+        let (rules, _) = parse(INPUT);
+        assert_eq!(
+            topological_order(&rules, &[97, 13, 75, 29, 47]),
+            Ok(vec![97, 75, 47, 29, 13])
+        );
+
+        let rules = HashSet::from([(1, 2), (2, 3)]);
+        assert!(is_ordered(&rules, &[1, 3]));
+        assert!(!is_ordered(&rules, &[3, 2]));
+        assert_eq!(topological_order(&rules, &[3, 2, 1]), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            topological_order(&rules, &[3, 1]),
+            Err(OrderError::Ambiguous(3, 1))
+        );
+
+        let rules = HashSet::from([(1, 2), (2, 3), (3, 1), (4, 1)]);
+        assert_eq!(
+            topological_order(&rules, &[4, 3, 2, 1]),
+            Err(OrderError::Cycle(vec![3, 1, 2, 3]))
+        );
+
+        let rules = HashSet::from([(1, 2), (2, 1), (2, 3)]);
+        assert_eq!(
+            topological_order(&rules, &[3, 1, 2]),
+            Err(OrderError::Cycle(vec![2, 1, 2]))
+        );
+        assert_eq!(
+            OrderError::Cycle(vec![1, 2, 3, 1]).to_string(),
+            "rules form a cycle 1 -> 2 -> 3 -> 1"
+        );
+    }
+
     #[test]
     fn test_part1() {
         let (rules, updates) = parse(INPUT);
@@ -135,4 +240,13 @@ mod tests {
         let incorrect_order_total = part2(&rules, updates);
         assert_eq!(incorrect_order_total, "123");
     }
+
+    #[test]
+    fn test_part2_cyclic_rules() {
+        let (rules, updates) = parse("1|2\n2|3\n3|1\n\n1,2\n3,2,1");
+        assert_eq!(
+            part2(&rules, updates),
+            "error: rules form a cycle 3 -> 1 -> 2 -> 3"
+        );
+    }
 }
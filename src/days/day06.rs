@@ -1,10 +1,13 @@
 use super::DayResult;
 use crate::utils::{
     bench::time_execution,
-    grid::{Coord, Direction::*, Grid, Robot},
+    cycle,
+    grid::{
+        Action, Agent, BitGrid, Colour, Coord, Direction::*, Edges, Grid, Recording, Simulation,
+    },
 };
 use rayon::prelude::*;
-use std::{collections::HashSet, fs};
+use std::{fs, io, time::Duration};
 
 pub fn run() -> DayResult {
     let input = fs::read_to_string("inputs/06.in").expect("Input file should be readable");
@@ -25,57 +28,260 @@ fn parse(input: &str) -> Grid<u8> {
     Grid::from(input)
 }
 
-fn patrol(lab: &Grid<u8>, start: Coord) -> Option<HashSet<Coord>> {
-    let mut guard = Robot::new(start, North);
-    let mut path = HashSet::with_capacity(5000);
+fn guard_rule(lab: &Grid<u8>, guard: &Agent) -> Action {
+    if lab.get(guard.next_pos()) == Some(&b'#') {
+        Action::Turn(90)
+    } else {
+        Action::Forward
+    }
+}
+
+fn next_guard(lab: &Grid<u8>, guard: &Agent) -> Option<Agent> {
+    let mut next = *guard;
+    match guard_rule(lab, guard) {
+        Action::Turn(degrees) => next.dir = guard.dir.rotated(degrees),
+        _ => next.pos = guard.next_pos(),
+    }
+    lab.contains(next.pos).then_some(next)
+}
+
+struct JumpTable {
+    rows: Vec<Vec<isize>>,
+    columns: Vec<Vec<isize>>,
+}
 
-    loop {
-        let next_pos = guard.next_pos();
-        if !lab.contains(next_pos) {
-            break;
+impl JumpTable {
+    fn new(lab: &Grid<u8>) -> Self {
+        let mut rows = vec![Vec::new(); lab.height()];
+        let mut columns = vec![Vec::new(); lab.width()];
+        for (r, obstacles) in rows.iter_mut().enumerate() {
+            for (c, _) in lab
+                .row(r)
+                .iter()
+                .enumerate()
+                .filter(|(_, &tile)| tile == b'#')
+            {
+                obstacles.push(c as isize);
+                columns[c].push(r as isize);
+            }
         }
-        if lab[next_pos] == b'#' {
-            guard.rotate(90);
+        Self { rows, columns }
+    }
+
+    fn jump(&self, guard: &Agent, obstruction: Coord) -> Option<Agent> {
+        let heading = Coord::from(guard.dir);
+        let (obstacles, along, step) = if heading.r == 0 {
+            (&self.rows[guard.pos.r as usize], guard.pos.c, heading.c)
         } else {
-            guard.locomote();
-        }
-        if !path.insert(guard) {
-            return None;
+            (&self.columns[guard.pos.c as usize], guard.pos.r, heading.r)
+        };
+        let split = obstacles.partition_point(|&obstacle| obstacle < along);
+        let wall = if step < 0 {
+            split.checked_sub(1).map(|i| obstacles[i])
+        } else {
+            obstacles.get(split).copied()
+        };
+        let mut distance = wall.map(|wall| (wall - along) * step);
+
+        let offset = obstruction - guard.pos;
+        let blocked = offset.r * heading.r + offset.c * heading.c;
+        if blocked > 0
+            && guard.pos.is_collinear(guard.next_pos(), obstruction)
+            && distance.is_none_or(|distance| blocked < distance)
+        {
+            distance = Some(blocked);
         }
+        distance.map(|distance| {
+            Agent::heading(guard.pos + heading * (distance - 1), guard.dir.turn_right())
+        })
     }
+}
+
+fn patrol(lab: &Grid<u8>, start: Coord) -> Option<BitGrid> {
+    let mut path = BitGrid::new(lab.height(), lab.width());
+    path.insert(start);
+    let is_loop = cycle::hashed(&Agent::heading(start, North), |guard| {
+        let next = next_guard(lab, guard)?;
+        path.insert(next.pos);
+        Some(next)
+    })
+    .is_some();
 
-    Some(path.into_iter().map(|robot| robot.position()).collect())
+    (!is_loop).then_some(path)
+}
+
+fn first_visits(lab: &Grid<u8>, start: Coord) -> Vec<(Coord, Agent)> {
+    let mut visited = BitGrid::new(lab.height(), lab.width());
+    let mut visits = Vec::new();
+    let mut guard = Agent::heading(start, North);
+    for _ in 0..=4 * lab.area() {
+        let Some(next) = next_guard(lab, &guard) else {
+            return visits;
+        };
+        if next.pos != guard.pos && visited.insert(next.pos) {
+            visits.push((next.pos, guard));
+        }
+        guard = next;
+    }
+    panic!("Input should not contain cycles");
 }
 
 fn part1(lab: &Grid<u8>) -> String {
     let start = lab.position(b'^').expect("Lab should have a guard");
     let tiles = patrol(lab, start);
-    let distinct_position_count = tiles.expect("Input should not contain cycles").len();
+    let distinct_position_count = tiles.expect("Input should not contain cycles").count_ones();
     format!("{distinct_position_count}")
 }
 
-fn part2(lab: &Grid<u8>) -> String {
-    let start = lab.position(b'^').expect("Lab should have a guard");
-    let tiles = patrol(lab, start).expect("Input should not contain cycles");
-
-    let potential_obstruction_count = tiles
+fn loop_obstructions(lab: &Grid<u8>, start: Coord) -> Vec<Coord> {
+    let jumps = JumpTable::new(lab);
+    first_visits(lab, start)
         .into_par_iter()
-        .filter(|&point| {
-            let mut g = lab.to_owned();
-            g[point] = b'#';
-            patrol(&g, start).is_none()
+        .filter(|(obstruction, guard)| {
+            cycle::brent(guard, |guard| jumps.jump(guard, *obstruction)).is_some()
         })
-        .count();
+        .map(|(obstruction, _)| obstruction)
+        .collect()
+}
 
+fn part2(lab: &Grid<u8>) -> String {
+    let start = lab.position(b'^').expect("Lab should have a guard");
+    let potential_obstruction_count = loop_obstructions(lab, start).len();
     format!("{potential_obstruction_count}")
 }
 
+pub fn report() -> io::Result<()> {
+    let input = fs::read_to_string("inputs/06.in")?;
+    let lab = parse(&input);
+    let start = lab.position(b'^').expect("Lab should have a guard");
+    let path = patrol(&lab, start).expect("Input should not contain cycles");
+    let obstructions = loop_obstructions(&lab, start);
+
+    for &obstruction in &obstructions {
+        let mut g = lab.to_owned();
+        g[obstruction] = b'#';
+        let cycle = cycle::brent(&Agent::heading(start, North), |guard| next_guard(&g, guard))
+            .expect("Obstruction should cause a loop");
+        println!(
+            "Obstruction at row {:>3}, col {:>3}: loop of {:>5} steps entered at step {:>5}",
+            obstruction.r, obstruction.c, cycle.length, cycle.start
+        );
+    }
+    println!("{} obstructions cause a loop\n", obstructions.len());
+
+    let rendered = lab
+        .render(|&cell| cell as char)
+        .overlay(path.iter_ones(), 'X', Some(Colour::Blue))
+        .overlay(obstructions, 'O', Some(Colour::Red))
+        .overlay([start], '^', Some(Colour::Green))
+        .rulers(true);
+    println!("{rendered}");
+
+    Ok(())
+}
+
+pub fn record(path: &str) -> io::Result<()> {
+    let input = fs::read_to_string("inputs/06.in")?;
+    let lab = parse(&input);
+    let start = lab.position(b'^').expect("Lab should have a guard");
+    let mut recording = Recording::new(Duration::from_millis(10));
+    recording.capture(&lab);
+
+    let mut sim = Simulation::new(lab, vec![Agent::heading(start, North)], Edges::Despawn);
+    sim.run(
+        |lab, guard| {
+            let action = guard_rule(lab, guard);
+            let (pos, dir) = match action {
+                Action::Turn(degrees) => (guard.pos, guard.dir.rotated(degrees)),
+                _ => (guard.next_pos(), guard.dir),
+            };
+            lab[guard.pos] = b'X';
+            if let Some(tile) = lab.get_mut(pos) {
+                *tile = dir.ascii_glyph();
+            }
+            action
+        },
+        |sim| {
+            recording.capture(sim.world());
+            true
+        },
+    );
+    recording.capture(sim.world());
+
+    recording.save(path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::{bench::time_execution, rng::Rng};
     use std::str;
 
-    const INPUT: &'static str = "....#.....\n.........#\n..........\n..#.......\n.......#..\n..........\n.#..^.....\n........#.\n#.........\n......#...";
+    fn loops_by_stepping(lab: &Grid<u8>, start: Coord, obstruction: Coord) -> bool {
+        let mut g = lab.to_owned();
+        g[obstruction] = b'#';
+        cycle::brent(&Agent::heading(start, North), |guard| next_guard(&g, guard)).is_some()
+    }
+
+    fn loops_by_jumping(jumps: &JumpTable, start: Coord, obstruction: Coord) -> bool {
+        cycle::brent(&Agent::heading(start, North), |guard| {
+            jumps.jump(guard, obstruction)
+        })
+        .is_some()
+    }
+
+    fn scattered_lab(size: usize, seed: u64) -> Grid<u8> {
+        let mut rng = Rng::new(seed);
+        let mut lab = String::new();
+        for r in 0..size {
+            for c in 0..size {
+                lab.push(match rng.below(32) {
+                    _ if (r, c) == (size / 2, size / 2) => '^',
+                    0..3 => '#',
+                    _ => '.',
+                });
+            }
+            lab.push('\n');
+        }
+        parse(&lab)
+    }
+
+    fn spiral_lab(size: usize, seed: u64) -> Grid<u8> {
+        let mut rng = Rng::new(seed);
+        let mut lab = Grid::new(size, size, b'.');
+        let mut route = BitGrid::new(size, size);
+        let start = Coord::from_usize(size / 2, size / 2).unwrap();
+        let mut guard = Agent::heading(start, North);
+        route.insert(start);
+        for segment in 2.. {
+            for _ in 0..segment / 2 * 2 {
+                guard.pos = guard.next_pos();
+                if !lab.contains(guard.pos) {
+                    break;
+                }
+                route.insert(guard.pos);
+            }
+            if !lab.contains(guard.next_pos()) {
+                break;
+            }
+            lab[guard.next_pos()] = b'#';
+            guard.dir = guard.dir.turn_right();
+        }
+        for _ in 0..size * size / 32 {
+            let pos = Coord::from_usize(
+                rng.below(size as u64) as usize,
+                rng.below(size as u64) as usize,
+            )
+            .unwrap();
+            if !route.is_set(pos) {
+                lab[pos] = b'#';
+            }
+        }
+        lab[start] = b'^';
+        lab
+    }
+
+    const INPUT: &str = "....#.....\n.........#\n..........\n..#.......\n.......#..\n..........\n.#..^.....\n........#.\n#.........\n......#...";
 
     #[test]
     fn test_parse() {
@@ -107,4 +313,63 @@ mod tests {
         let potential_obstruction_count = part2(&lab);
         assert_eq!(potential_obstruction_count, "6");
     }
+
+    #[test]
+    fn test_jump_table_matches_stepping() {
+        let labs = [
+            parse(INPUT),
+            scattered_lab(24, 1),
+            scattered_lab(24, 7),
+            scattered_lab(40, 2024),
+            spiral_lab(24, 5),
+            spiral_lab(31, 8),
+        ];
+        for lab in labs {
+            let start = lab.position(b'^').unwrap();
+            let jumps = JumpTable::new(&lab);
+            for (obstruction, &tile) in lab.enumerate_cells() {
+                if tile == b'#' {
+                    continue;
+                }
+                assert_eq!(
+                    loops_by_jumping(&jumps, start, obstruction),
+                    loops_by_stepping(&lab, start, obstruction),
+                    "Obstruction at {obstruction:?}"
+                );
+            }
+            let tiles = patrol(&lab, start).unwrap();
+            let loop_count = tiles
+                .iter_ones()
+                .filter(|&obstruction| loops_by_stepping(&lab, start, obstruction))
+                .count();
+            assert_eq!(part2(&lab), format!("{loop_count}"));
+        }
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with --release -- --ignored"]
+    fn bench_jumping_against_stepping() {
+        let lab = spiral_lab(130, 7);
+        let start = lab.position(b'^').unwrap();
+
+        let stepping = time_execution(|| {
+            first_visits(&lab, start)
+                .into_iter()
+                .filter(|&(obstruction, _)| loops_by_stepping(&lab, start, obstruction))
+                .count()
+        });
+        let jumping = time_execution(|| {
+            let jumps = JumpTable::new(&lab);
+            first_visits(&lab, start)
+                .into_iter()
+                .filter(|(obstruction, guard)| {
+                    cycle::brent(guard, |guard| jumps.jump(guard, *obstruction)).is_some()
+                })
+                .count()
+        });
+
+        assert_eq!(stepping.result, jumping.result);
+        println!("stepping: {:?}", stepping.duration);
+        println!("jumping:  {:?}", jumping.duration);
+    }
 }
@@ -1,7 +1,8 @@
 use super::DayResult;
-use crate::utils::bench::time_execution;
+use crate::utils::{bench::time_execution, scan};
+use itertools::Itertools;
 use rayon::prelude::*;
-use std::fs;
+use std::{fmt, fs, io, str::FromStr};
 
 pub fn run() -> DayResult {
     let input = fs::read_to_string("inputs/07.in").expect("Input file should be readable");
@@ -28,65 +29,208 @@ fn parse(input: &str) -> Vec<(u64, Vec<u64>)> {
             let test_value = test_value
                 .parse()
                 .expect("Test value should be a positive integer");
-            let numbers = numbers
-                .split(' ')
-                .map(|num| num.parse().expect("Numbers should be positive integers"))
-                .collect();
+            let numbers = scan::separated(numbers.as_bytes(), b" ")
+                .collect::<Result<_, _>>()
+                .expect("Numbers should be positive integers separated by spaces");
             (test_value, numbers)
         })
         .collect()
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Mul,
+    Concat(u64),
+    Sub,
+    Xor,
+}
+
+impl Operator {
+    fn concat_shift(base: u64, operand: u64) -> Option<u64> {
+        let mut shift = base;
+        while shift <= operand {
+            shift = shift.checked_mul(base)?;
+        }
+        Some(shift)
+    }
+
+    fn apply(self, acc: u64, operand: u64) -> Option<u64> {
+        match self {
+            Operator::Add => acc.checked_add(operand),
+            Operator::Mul => acc.checked_mul(operand),
+            Operator::Concat(base) => Self::concat_shift(base, operand)?
+                .checked_mul(acc)?
+                .checked_add(operand),
+            Operator::Sub => acc.checked_sub(operand),
+            Operator::Xor => Some(acc ^ operand),
+        }
+    }
+
+    fn invert(self, result: u64, operand: u64) -> Option<u64> {
+        match self {
+            Operator::Add => result.checked_sub(operand),
+            Operator::Mul => {
+                (operand != 0 && result.is_multiple_of(operand)).then(|| result / operand)
+            }
+            Operator::Concat(base) => {
+                let shift = Self::concat_shift(base, operand)?;
+                (result % shift == operand).then(|| result / shift)
+            }
+            Operator::Sub => result.checked_add(operand),
+            Operator::Xor => Some(result ^ operand),
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operator::Add => write!(f, "+"),
+            Operator::Mul => write!(f, "*"),
+            Operator::Concat(10) => write!(f, "||"),
+            Operator::Concat(base) => write!(f, "||{base}"),
+            Operator::Sub => write!(f, "-"),
+            Operator::Xor => write!(f, "^"),
+        }
+    }
+}
+
+impl FromStr for Operator {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "+" => Ok(Operator::Add),
+            "*" => Ok(Operator::Mul),
+            "||" => Ok(Operator::Concat(10)),
+            "-" => Ok(Operator::Sub),
+            "^" => Ok(Operator::Xor),
+            _ => match s.strip_prefix("||").map(str::parse) {
+                Some(Ok(base)) if base >= 2 => Ok(Operator::Concat(base)),
+                _ => Err(format!("Unknown operator {s}")),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Operators(Vec<Operator>);
+
+const ADD_MUL: &[Operator] = &[Operator::Add, Operator::Mul];
+const ADD_MUL_CONCAT: &[Operator] = &[Operator::Add, Operator::Mul, Operator::Concat(10)];
+
+impl FromStr for Operators {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|op| op.trim().parse())
+            .collect::<Result<_, _>>()
+            .map(Operators)
+    }
+}
+
+fn solve_backward(target: u64, numbers: &[u64], operators: &[Operator]) -> Option<Vec<Operator>> {
+    let (&last, rest) = numbers.split_last()?;
+    if rest.is_empty() {
+        return (last == target).then(Vec::new);
+    }
+    operators.iter().find_map(|&op| {
+        let mut ops = if op == Operator::Mul && last == 0 {
+            if target != 0 {
+                return None;
+            }
+            any_expression(rest, operators)?
+        } else {
+            solve_backward(op.invert(target, last)?, rest, operators)?
+        };
+        ops.push(op);
+        Some(ops)
+    })
+}
+
+fn any_expression(numbers: &[u64], operators: &[Operator]) -> Option<Vec<Operator>> {
+    fn search(acc: u64, rest: &[u64], operators: &[Operator], ops: &mut Vec<Operator>) -> bool {
+        let Some((&operand, rest)) = rest.split_first() else {
+            return true;
+        };
+        operators.iter().any(|&op| {
+            let Some(acc) = op.apply(acc, operand) else {
+                return false;
+            };
+            ops.push(op);
+            if search(acc, rest, operators, ops) {
+                return true;
+            }
+            ops.pop();
+            false
+        })
+    }
+
+    let (&first, rest) = numbers.split_first()?;
+    let mut ops = Vec::new();
+    search(first, rest, operators, &mut ops).then_some(ops)
+}
+
+fn expression(numbers: &[u64], operators: &[Operator]) -> String {
+    let mut expression = numbers[0].to_string();
+    for (op, operand) in operators.iter().zip(&numbers[1..]) {
+        expression.push_str(&format!(" {op} {operand}"));
+    }
+    expression
+}
+
+fn total_calibration_result(
+    calibration_equations: &[(u64, Vec<u64>)],
+    operators: &[Operator],
+) -> u64 {
+    calibration_equations
+        .par_iter()
+        .filter(|(test_value, numbers)| solve_backward(*test_value, numbers, operators).is_some())
+        .map(|(test_value, _)| test_value)
+        .sum()
+}
+
 fn part1(calibration_equations: &[(u64, Vec<u64>)]) -> String {
-    let total_calibration_result = solve(calibration_equations, false);
+    let total_calibration_result = total_calibration_result(calibration_equations, ADD_MUL);
     format!("{total_calibration_result}")
 }
 
 fn part2(calibration_equations: &[(u64, Vec<u64>)]) -> String {
-    let total_calibration_result = solve(calibration_equations, true);
+    let total_calibration_result = total_calibration_result(calibration_equations, ADD_MUL_CONCAT);
     format!("{total_calibration_result}")
 }
 
-fn solve(calibration_equations: &[(u64, Vec<u64>)], is_part2: bool) -> u64 {
-    fn is_possible(acc: u64, arr: &[u64], i: usize, target: u64, is_part2: bool) -> bool {
-        if i >= arr.len() {
-            acc == target
-        } else if acc > target {
-            false
-        } else {
-            is_possible(acc + arr[i], arr, i + 1, target, is_part2)
-                || is_possible(acc * arr[i], arr, i + 1, target, is_part2)
-                || if is_part2 {
-                    is_possible(
-                        acc * 10_u64.pow(arr[i].ilog10() + 1) + arr[i],
-                        arr,
-                        i + 1,
-                        target,
-                        is_part2,
-                    )
-                } else {
-                    false
-                }
+pub fn explain(operators: Option<Operators>) -> io::Result<()> {
+    let input = fs::read_to_string("inputs/07.in")?;
+    let calibration_equations = parse(&input);
+    let operators = operators.map_or(ADD_MUL_CONCAT.to_vec(), |Operators(ops)| ops);
+
+    let mut total = 0;
+    for (test_value, numbers) in &calibration_equations {
+        match solve_backward(*test_value, numbers, &operators) {
+            Some(ops) => {
+                total += test_value;
+                println!("{test_value} = {}", expression(numbers, &ops));
+            }
+            None => println!("{test_value} cannot be made from {numbers:?}"),
         }
     }
+    println!(
+        "\nTotal calibration result with {}: {total}",
+        operators.iter().join(" ")
+    );
 
-    calibration_equations
-        .par_iter()
-        .filter_map(|(test_value, numbers)| {
-            if is_possible(numbers[0], numbers, 1, *test_value, is_part2) {
-                Some(test_value)
-            } else {
-                None
-            }
-        })
-        .sum()
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::rng::Rng;
 
-    const INPUT: &'static str = "190: 10 19\n3267: 81 40 27\n83: 17 5\n156: 15 6\n7290: 6 8 6 15\n161011: 16 10 13\n192: 17 8 14\n21037: 9 7 18 13\n292: 11 6 16 20";
+    const INPUT: &str = "190: 10 19\n3267: 81 40 27\n83: 17 5\n156: 15 6\n7290: 6 8 6 15\n161011: 16 10 13\n192: 17 8 14\n21037: 9 7 18 13\n292: 11 6 16 20";
 
     #[test]
     fn test_parse() {
@@ -103,6 +247,176 @@ mod tests {
         assert_eq!(calibration_equations[8], (292, vec![11, 6, 16, 20]));
     }
 
+    impl Operator {
+        fn is_monotonic(self) -> bool {
+            matches!(self, Operator::Add | Operator::Mul | Operator::Concat(_))
+        }
+    }
+
+    fn solve_forward(target: u64, numbers: &[u64], operators: &[Operator]) -> bool {
+        fn search(
+            acc: u64,
+            rest: &[u64],
+            target: u64,
+            operators: &[Operator],
+            prune: bool,
+        ) -> bool {
+            match rest.split_first() {
+                None => acc == target,
+                Some(_) if prune && acc > target => false,
+                Some((&operand, rest)) => operators.iter().any(|op| {
+                    op.apply(acc, operand)
+                        .is_some_and(|acc| search(acc, rest, target, operators, prune))
+                }),
+            }
+        }
+
+        let prune = !numbers.contains(&0) && operators.iter().all(|op| op.is_monotonic());
+        numbers
+            .split_first()
+            .is_some_and(|(&first, rest)| search(first, rest, target, operators, prune))
+    }
+
+    #[test]
+    fn test_operators() {
+        assert_eq!(Operator::Concat(10).apply(12, 345), Some(12345));
+        assert_eq!(Operator::Concat(10).invert(12345, 345), Some(12));
+        assert_eq!(Operator::Concat(10).invert(12345, 45), Some(123));
+        assert_eq!(Operator::Concat(10).invert(12345, 35), None);
+        assert_eq!(Operator::Concat(10).apply(7, 10), Some(710));
+        assert_eq!(Operator::Concat(2).apply(0b101, 0b11), Some(0b10111));
+        assert_eq!(Operator::Concat(2).invert(0b10111, 0b11), Some(0b101));
+        assert_eq!(Operator::Mul.invert(156, 7), None);
+        assert_eq!(Operator::Sub.apply(3, 5), None);
+        assert_eq!(
+            "+, *, ||, ||2, -, ^".parse(),
+            Ok(Operators(vec![
+                Operator::Add,
+                Operator::Mul,
+                Operator::Concat(10),
+                Operator::Concat(2),
+                Operator::Sub,
+                Operator::Xor
+            ]))
+        );
+        assert_eq!(
+            "+,||1".parse::<Operators>(),
+            Err("Unknown operator ||1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_expression() {
+        let explained: Vec<_> = parse(INPUT)
+            .iter()
+            .filter_map(|(test_value, numbers)| {
+                let ops = solve_backward(*test_value, numbers, ADD_MUL_CONCAT)?;
+                Some(format!("{test_value} = {}", expression(numbers, &ops)))
+            })
+            .collect();
+        assert_eq!(
+            explained,
+            vec![
+                "190 = 10 * 19",
+                "3267 = 81 * 40 + 27",
+                "156 = 15 || 6",
+                "7290 = 6 * 8 || 6 * 15",
+                "192 = 17 || 8 + 14",
+                "292 = 11 + 6 * 16 + 20"
+            ]
+        );
+        assert_eq!(
+            solve_backward(0, &[1, 5, 0], &[Operator::Sub, Operator::Mul]),
+            Some(vec![Operator::Mul, Operator::Mul])
+        );
+        assert_eq!(solve_backward(0, &[1, 5, 0], &[Operator::Sub]), None);
+        assert_eq!(
+            solve_backward(7, &[7, 0], ADD_MUL),
+            Some(vec![Operator::Add])
+        );
+        assert_eq!(
+            solve_backward(3, &[9, 3, 5], &[Operator::Sub, Operator::Xor]),
+            Some(vec![Operator::Sub, Operator::Xor])
+        );
+    }
+
+    #[test]
+    fn test_backward_matches_forward() {
+        let mut rng = Rng::new(0x0707_2024);
+        let all = [
+            Operator::Add,
+            Operator::Mul,
+            Operator::Concat(10),
+            Operator::Concat(3),
+            Operator::Sub,
+            Operator::Xor,
+        ];
+        for _ in 0..5000 {
+            let operators: Vec<_> = all.iter().copied().filter(|_| rng.below(2) == 0).collect();
+            let numbers: Vec<u64> = (0..=rng.below(5)).map(|_| rng.below(30)).collect();
+            let target = if rng.below(2) == 0 {
+                rng.below(5000)
+            } else {
+                let mut acc = numbers[0];
+                for &n in &numbers[1..] {
+                    let op = all[rng.below(all.len() as u64) as usize];
+                    acc = op.apply(acc, n).unwrap_or(acc);
+                }
+                acc
+            };
+            let backward = solve_backward(target, &numbers, &operators);
+            assert_eq!(
+                backward.is_some(),
+                solve_forward(target, &numbers, &operators),
+                "{target} {numbers:?} {operators:?}"
+            );
+            if let Some(ops) = backward {
+                let value = numbers[1..]
+                    .iter()
+                    .zip(&ops)
+                    .try_fold(numbers[0], |acc, (&n, op)| op.apply(acc, n));
+                assert_eq!(value, Some(target));
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with --release -- --ignored"]
+    fn bench_backward_against_forward() {
+        let mut rng = Rng::new(42);
+        let equations: Vec<(u64, Vec<u64>)> = (0..850)
+            .map(|_| {
+                let numbers: Vec<u64> = (0..3 + rng.below(10)).map(|_| 1 + rng.below(99)).collect();
+                let target = numbers[1..]
+                    .iter()
+                    .try_fold(numbers[0], |acc, &n| {
+                        ADD_MUL_CONCAT[rng.below(3) as usize].apply(acc, n)
+                    })
+                    .unwrap_or(u64::MAX / 2);
+                (target, numbers)
+            })
+            .collect();
+
+        let forward = time_execution(|| {
+            equations
+                .iter()
+                .filter(|(target, numbers)| solve_forward(*target, numbers, ADD_MUL_CONCAT))
+                .count()
+        });
+        let backward = time_execution(|| {
+            equations
+                .iter()
+                .filter(|(target, numbers)| {
+                    solve_backward(*target, numbers, ADD_MUL_CONCAT).is_some()
+                })
+                .count()
+        });
+
+        assert_eq!(forward.result, backward.result);
+        println!("forward:  {:?}", forward.duration);
+        println!("backward: {:?}", backward.duration);
+    }
+
     #[test]
     fn test_part1() {
         let calibration_equations = parse(INPUT);
@@ -1,12 +1,13 @@
 use super::DayResult;
 use crate::utils::{
     bench::time_execution,
-    grid::{Coord, Grid},
+    grid::{Colour, Coord, Grid, SparseGrid},
 };
 use itertools::Itertools;
 use std::{
-    collections::{HashMap, HashSet},
-    fs,
+    collections::{BTreeMap, HashSet},
+    fs, io,
+    str::FromStr,
 };
 
 pub fn run() -> DayResult {
@@ -28,58 +29,188 @@ fn parse(input: &str) -> Grid<u8> {
     Grid::from(input)
 }
 
-fn find_antennae(city: &Grid<u8>) -> HashMap<u8, Vec<Coord>> {
-    let mut antenna_map: HashMap<u8, Vec<Coord>> = HashMap::new();
+fn find_antennae(city: &Grid<u8>) -> BTreeMap<u8, Vec<Coord>> {
+    let mut antenna_map: BTreeMap<u8, Vec<Coord>> = BTreeMap::new();
     city.enumerate_cells()
         .filter(|(_, &ch)| ch != b'.' && ch != b'#')
         .for_each(|(coord, &ch)| antenna_map.entry(ch).or_default().push(coord));
     antenna_map
 }
 
-fn solve(city: &Grid<u8>, is_part2: bool) -> usize {
-    let antenna_map = find_antennae(city);
-    let mut antinodes = HashSet::new();
-
-    for antennae in antenna_map.into_values() {
-        let k = antennae.len();
-        for (i, j) in (0..k).tuple_combinations() {
-            let (tower1, tower2) = (antennae[i], antennae[j]);
-            let offset = tower1 - tower2;
-
-            for direction in [1, -1] {
-                let start = if direction == 1 { tower1 } else { tower2 };
-                let mut m = isize::from(!is_part2);
-                loop {
-                    let antinode = start + offset * m * direction;
-                    if !city.contains(antinode) || (!is_part2 && m > 1) {
-                        break;
-                    }
-                    antinodes.insert(antinode);
-                    m += 1;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resonance {
+    Multiples(Vec<isize>),
+    Lattice,
+    Ratio(isize, isize),
+}
+
+impl Resonance {
+    fn line_positions(&self) -> Vec<(isize, isize)> {
+        match self {
+            Resonance::Multiples(multiples) => multiples
+                .iter()
+                .flat_map(|&k| [(-k, 1), (1 + k, 1)])
+                .collect(),
+            Resonance::Lattice => Vec::new(),
+            &Resonance::Ratio(p, q) => {
+                let mut positions = vec![(p, p + q), (q, p + q)];
+                if p != q {
+                    positions.extend([(p, p - q), (-q, p - q)]);
+                }
+                positions
+            }
+        }
+    }
+
+    fn antinodes(&self, city: &Grid<u8>, tower1: Coord, tower2: Coord) -> Vec<Coord> {
+        let offset = tower2 - tower1;
+        if *self == Resonance::Lattice {
+            let step = offset.reduced();
+            return city
+                .ray(tower1, step)
+                .chain(city.ray(tower1 - step, -step))
+                .collect();
+        }
+        self.line_positions()
+            .into_iter()
+            .filter_map(|(num, den)| {
+                let scaled = offset * num;
+                let on_lattice = scaled.r % den == 0 && scaled.c % den == 0;
+                on_lattice.then(|| tower1 + scaled / den)
+            })
+            .filter(|&coord| city.contains(coord))
+            .collect()
+    }
+}
+
+impl FromStr for Resonance {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid resonance rule {s}");
+        let (name, args) = s.split_once(':').unwrap_or((s, ""));
+        match name {
+            "lattice" if args.is_empty() => Ok(Resonance::Lattice),
+            "multiples" => args
+                .split(',')
+                .map(|k| k.trim().parse().map_err(|_| invalid()))
+                .collect::<Result<_, _>>()
+                .map(Resonance::Multiples),
+            "ratio" => {
+                let (p, q) = args.split_once('/').unwrap_or((args, "1"));
+                match (p.trim().parse(), q.trim().parse()) {
+                    (Ok(p), Ok(q)) if p >= q && q > 0 => Ok(Resonance::Ratio(p, q)),
+                    _ => Err(invalid()),
                 }
             }
+            _ => Err(invalid()),
         }
     }
+}
 
-    antinodes.len()
+fn resonate(
+    city: &Grid<u8>,
+    antenna_map: &BTreeMap<u8, Vec<Coord>>,
+    rule: &Resonance,
+) -> BTreeMap<u8, HashSet<Coord>> {
+    antenna_map
+        .iter()
+        .map(|(&frequency, antennae)| {
+            let antinodes = antennae
+                .iter()
+                .tuple_combinations()
+                .flat_map(|(&tower1, &tower2)| rule.antinodes(city, tower1, tower2))
+                .collect();
+            (frequency, antinodes)
+        })
+        .collect()
+}
+
+fn antinode_count(city: &Grid<u8>, rule: &Resonance) -> usize {
+    resonate(city, &find_antennae(city), rule)
+        .into_values()
+        .flatten()
+        .collect::<HashSet<_>>()
+        .len()
 }
 
 fn part1(city: &Grid<u8>) -> String {
-    let antinode_count = solve(city, false);
+    let antinode_count = antinode_count(city, &Resonance::Multiples(vec![1]));
     format!("{antinode_count}")
 }
 
 fn part2(city: &Grid<u8>) -> String {
-    let antinode_count = solve(city, true);
+    let antinode_count = antinode_count(city, &Resonance::Lattice);
     format!("{antinode_count}")
 }
 
+const PALETTE: [Colour; 6] = [
+    Colour::Red,
+    Colour::Green,
+    Colour::Yellow,
+    Colour::Blue,
+    Colour::Magenta,
+    Colour::Cyan,
+];
+
+pub fn report(rule: Option<Resonance>) -> io::Result<()> {
+    let input = fs::read_to_string("inputs/08.in")?;
+    let city = parse(&input);
+    let rule = rule.unwrap_or(Resonance::Lattice);
+    let antenna_map = find_antennae(&city);
+    let antinode_map = resonate(&city, &antenna_map, &rule);
+
+    println!("Frequency  Antennae  Pairs  Antinodes  Shared");
+    for (frequency, antinodes) in &antinode_map {
+        let shared = antinodes
+            .iter()
+            .filter(|coord| {
+                antinode_map
+                    .iter()
+                    .any(|(other, nodes)| other != frequency && nodes.contains(coord))
+            })
+            .count();
+        let k = antenna_map[frequency].len();
+        println!(
+            "{:<9}  {k:>8}  {:>5}  {:>9}  {shared:>6}",
+            *frequency as char,
+            k * k.saturating_sub(1) / 2,
+            antinodes.len()
+        );
+    }
+
+    let mut counts = SparseGrid::new(0);
+    for &coord in antinode_map.values().flatten() {
+        counts.set(coord, counts[coord] + 1);
+    }
+    let mut rendered = city.render(|_| '.').rulers(true);
+    for (i, (frequency, antinodes)) in antinode_map.iter().enumerate() {
+        let colour = Some(PALETTE[i % PALETTE.len()]);
+        rendered = rendered
+            .overlay(antinodes.iter().copied(), '#', colour)
+            .overlay(
+                antenna_map[frequency].iter().copied(),
+                *frequency as char,
+                colour,
+            );
+    }
+    let overlapping = counts
+        .enumerate_cells()
+        .filter(|&(coord, &count)| count > 1 && matches!(city[coord], b'.' | b'#'))
+        .map(|(coord, _)| coord);
+    rendered = rendered.overlay(overlapping, '*', Some(Colour::BrightWhite));
+    println!("\n{rendered}");
+    println!("{} antinodes with {rule:?}", antinode_count(&city, &rule));
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::str;
 
-    const INPUT: &'static str = "............\n........0...\n.....0......\n.......0....\n....0.......\n......A.....\n............\n............\n........A...\n.........A..\n............\n............\n";
+    const INPUT: &str = "............\n........0...\n.....0......\n.......0....\n....0.......\n......A.....\n............\n............\n........A...\n.........A..\n............\n............\n";
 
     #[test]
     fn test_parse() {
@@ -100,6 +231,56 @@ mod tests {
         assert_eq!(str::from_utf8(city.row(11)).unwrap(), "............");
     }
 
+    #[test]
+    fn test_resonance_rules() {
+        let city = Grid::new(10, 10, b'.');
+        let antinodes = |rule: Resonance, tower1: Coord, tower2: Coord| {
+            let mut antinodes = rule.antinodes(&city, tower1, tower2);
+            antinodes.sort_by_key(|coord| (coord.r, coord.c));
+            antinodes
+        };
+
+        let (tower1, tower2) = (Coord::new(3, 4), Coord::new(5, 5));
+        let fixed = antinodes(Resonance::Multiples(vec![1]), tower1, tower2);
+        assert_eq!(fixed, vec![Coord::new(1, 3), Coord::new(7, 6)]);
+        assert_eq!(antinodes(Resonance::Ratio(2, 1), tower1, tower2), fixed);
+        assert_eq!(
+            antinodes(Resonance::Multiples(vec![1, 2]), tower1, tower2),
+            vec![Coord::new(1, 3), Coord::new(7, 6), Coord::new(9, 7)]
+        );
+
+        let (tower1, tower2) = (Coord::new(3, 3), Coord::new(6, 6));
+        assert_eq!(
+            antinodes(Resonance::Ratio(2, 1), tower1, tower2),
+            [0, 4, 5, 9].map(|i| Coord::new(i, i))
+        );
+        assert_eq!(antinodes(Resonance::Ratio(3, 2), tower1, tower2), vec![]);
+        assert_eq!(
+            antinodes(Resonance::Lattice, tower1, tower2),
+            (0..10).map(|i| Coord::new(i, i)).collect::<Vec<_>>()
+        );
+
+        assert_eq!("lattice".parse(), Ok(Resonance::Lattice));
+        assert_eq!(
+            "multiples:1, 2".parse(),
+            Ok(Resonance::Multiples(vec![1, 2]))
+        );
+        assert_eq!("ratio:3/2".parse(), Ok(Resonance::Ratio(3, 2)));
+        assert_eq!("ratio:2".parse(), Ok(Resonance::Ratio(2, 1)));
+        assert!("ratio:1/2".parse::<Resonance>().is_err());
+    }
+
+    #[test]
+    fn test_frequency_antinodes() {
+        let city = parse(INPUT);
+        let antinode_map = resonate(&city, &find_antennae(&city), &Resonance::Multiples(vec![1]));
+        let counts: Vec<_> = antinode_map
+            .iter()
+            .map(|(&frequency, antinodes)| (frequency, antinodes.len()))
+            .collect();
+        assert_eq!(counts, vec![(b'0', 10), (b'A', 5)]);
+    }
+
     #[test]
     fn test_part1() {
         let city = parse(INPUT);
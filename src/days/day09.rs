@@ -1,14 +1,14 @@
 use super::DayResult;
 use crate::utils::bench::time_execution;
-use std::{fs, iter::repeat};
+use std::{cmp::Reverse, collections::BinaryHeap, fs, io, str::FromStr};
 
 pub fn run() -> DayResult {
     let input = fs::read_to_string("inputs/09.in").expect("Input file should be readable");
 
     let parsed = time_execution(|| parse(&input));
-    let (disk, files, holes) = parsed.result;
-    let part1 = time_execution(|| part1(disk));
-    let part2 = time_execution(|| part2(files, holes));
+    let disk = parsed.result;
+    let part1 = time_execution(|| part1(&disk));
+    let part2 = time_execution(|| part2(&disk));
 
     DayResult {
         parse_duration: parsed.duration,
@@ -17,101 +17,309 @@ pub fn run() -> DayResult {
     }
 }
 
-#[allow(clippy::type_complexity)]
-fn parse(input: &str) -> (Vec<i64>, Vec<(u64, u64)>, Vec<(u64, u64)>) {
-    let (mut disk, mut files, mut holes) = (Vec::new(), Vec::new(), Vec::new());
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Span {
+    pos: u64,
+    len: u64,
+}
+
+impl Span {
+    fn end(self) -> u64 {
+        self.pos + self.len
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Disk {
+    size: u64,
+    files: Vec<Vec<Span>>,
+    holes: Vec<Span>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    Fragment,
+    WholeFile,
+}
+
+impl FromStr for Policy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fragment" => Ok(Policy::Fragment),
+            "whole-file" => Ok(Policy::WholeFile),
+            _ => Err(format!("Unknown compaction policy {s}")),
+        }
+    }
+}
+
+fn parse(input: &str) -> Disk {
+    let (mut files, mut holes) = (Vec::new(), Vec::new());
     let mut pos = 0;
 
     for (i, c) in input.trim().bytes().enumerate() {
-        let len = u64::from(c - b'0');
+        let span = Span {
+            pos,
+            len: u64::from(c - b'0'),
+        };
         if i % 2 == 0 {
-            disk.extend(repeat(i as i64 / 2).take(len as usize));
-            files.push((pos, len));
+            files.push(vec![span]);
         } else {
-            disk.extend(repeat(-1).take(len as usize));
-            holes.push((pos, len));
+            holes.push(span);
         }
-        pos += len;
+        pos += span.len;
     }
 
-    (disk, files, holes)
+    Disk {
+        size: pos,
+        files,
+        holes,
+    }
 }
 
-fn part1(mut disk: Vec<i64>) -> String {
-    let mut hole_idx = disk
-        .iter()
-        .position(|&block| block < 0)
-        .expect("Input should contain empty blocks");
-
-    let mut file_idx = disk
-        .iter()
-        .rposition(|&block| block >= 0)
-        .expect("Input should contain files");
-
-    while file_idx > hole_idx {
-        disk.swap(hole_idx, file_idx);
-        while file_idx > hole_idx && disk[hole_idx] >= 0 {
-            hole_idx += 1;
+impl Disk {
+    fn compacted<F>(&self, policy: Policy, on_move: F) -> Disk
+    where
+        F: FnMut(&Disk),
+    {
+        let mut disk = self.clone();
+        match policy {
+            Policy::Fragment => disk.fragment(on_move),
+            Policy::WholeFile => disk.move_whole_files(on_move),
         }
-        while file_idx > hole_idx && disk[file_idx] < 0 {
-            file_idx -= 1;
+        disk.holes = disk.free_spans();
+        disk
+    }
+
+    fn free_spans(&self) -> Vec<Span> {
+        let mut used: Vec<Span> = self.files.iter().flatten().copied().collect();
+        used.sort_unstable_by_key(|span| span.pos);
+        let mut free = Vec::new();
+        let mut pos = 0;
+        for span in used.into_iter().chain([Span {
+            pos: self.size,
+            len: 0,
+        }]) {
+            if span.pos > pos {
+                free.push(Span {
+                    pos,
+                    len: span.pos - pos,
+                });
+            }
+            pos = pos.max(span.end());
         }
+        free
     }
 
-    let checksum: i64 = disk[..hole_idx]
-        .iter()
-        .enumerate()
-        .map(|(i, &block)| i as i64 * block)
-        .sum();
-    format!("{checksum}")
-}
+    fn fragment<F: FnMut(&Disk)>(&mut self, mut on_move: F) {
+        let mut holes = self.holes.clone();
+        let mut h = 0;
+        for id in (0..self.files.len()).rev() {
+            let mut remaining = *self.files[id].last().expect("Files should have a span");
+            while remaining.len > 0 {
+                while h < holes.len() && holes[h].len == 0 {
+                    h += 1;
+                }
+                if h == holes.len() || holes[h].pos > remaining.pos {
+                    break;
+                }
+                let moved = Span {
+                    pos: holes[h].pos,
+                    len: holes[h].len.min(remaining.len),
+                };
+                holes[h].pos += moved.len;
+                holes[h].len -= moved.len;
+                remaining.len -= moved.len;
 
-fn part2(mut files: Vec<(u64, u64)>, mut holes: Vec<(u64, u64)>) -> String {
-    for (file_pos, file_len) in files.iter_mut().rev() {
-        for (hole_pos, hole_len) in &mut holes {
-            if *hole_pos > *file_pos {
-                break;
+                let file = &mut self.files[id];
+                file.pop();
+                file.push(moved);
+                if remaining.len > 0 {
+                    file.push(remaining);
+                }
+                on_move(self);
             }
-            if *file_len <= *hole_len {
-                *file_pos = *hole_pos;
-                *hole_pos += *file_len;
-                *hole_len -= *file_len;
-                break;
+        }
+    }
+
+    fn move_whole_files<F: FnMut(&Disk)>(&mut self, mut on_move: F) {
+        let max_len = self.holes.iter().map(|hole| hole.len).max().unwrap_or(0) as usize;
+        let mut free: Vec<BinaryHeap<Reverse<u64>>> = vec![BinaryHeap::new(); max_len + 1];
+        for hole in self.holes.iter().filter(|hole| hole.len > 0) {
+            free[hole.len as usize].push(Reverse(hole.pos));
+        }
+
+        for id in (0..self.files.len()).rev() {
+            let span = self.files[id][0];
+            if span.len == 0 || span.len as usize > max_len {
+                continue;
+            }
+            let leftmost = (span.len as usize..=max_len)
+                .filter_map(|len| free[len].peek().map(|&Reverse(pos)| (pos, len)))
+                .filter(|&(pos, _)| pos < span.pos)
+                .min();
+            let Some((pos, len)) = leftmost else {
+                continue;
+            };
+
+            free[len].pop();
+            let rest = len - span.len as usize;
+            if rest > 0 {
+                free[rest].push(Reverse(pos + span.len));
+            }
+            self.files[id] = vec![Span { pos, len: span.len }];
+            on_move(self);
+        }
+    }
+
+    fn checksum(&self) -> u64 {
+        self.files
+            .iter()
+            .enumerate()
+            .flat_map(|(id, spans)| spans.iter().map(move |span| (id as u64, span)))
+            .map(|(id, span)| {
+                id * (span.pos * span.len + span.len * span.len.saturating_sub(1) / 2)
+            })
+            .sum()
+    }
+
+    fn layout(&self) -> String {
+        let mut blocks = vec!['.'; self.size as usize];
+        for (id, spans) in self.files.iter().enumerate() {
+            let glyph = u32::try_from(id)
+                .ok()
+                .and_then(|id| char::from_digit(id, 36))
+                .expect("Layout should only show files with single-digit base 36 ids");
+            for span in spans {
+                blocks[span.pos as usize..span.end() as usize].fill(glyph);
             }
         }
+        blocks.into_iter().collect()
     }
+}
 
-    let checksum: u64 = files
-        .iter()
-        .enumerate()
-        .map(|(i, &(file_pos, file_len))| (file_pos..file_pos + file_len).sum::<u64>() * i as u64)
-        .sum();
+fn part1(disk: &Disk) -> String {
+    let checksum = disk.compacted(Policy::Fragment, |_| {}).checksum();
     format!("{checksum}")
 }
 
+fn part2(disk: &Disk) -> String {
+    let checksum = disk.compacted(Policy::WholeFile, |_| {}).checksum();
+    format!("{checksum}")
+}
+
+const MAX_TRACE_BLOCKS: u64 = 256;
+const MAX_TRACE_FILES: usize = 36;
+
+pub fn trace(policy: Option<Policy>) -> io::Result<()> {
+    let input = fs::read_to_string("inputs/09.in")?;
+    let disk = parse(&input);
+    if disk.size > MAX_TRACE_BLOCKS {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "Disk has {} blocks, tracing is limited to {MAX_TRACE_BLOCKS}",
+                disk.size
+            ),
+        ));
+    }
+    if disk.files.len() > MAX_TRACE_FILES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "Disk has {} files, tracing is limited to {MAX_TRACE_FILES}",
+                disk.files.len()
+            ),
+        ));
+    }
+
+    let policy = policy.unwrap_or(Policy::WholeFile);
+    println!("{:>5} {}", 0, disk.layout());
+    let mut moves = 0;
+    let compacted = disk.compacted(policy, |disk| {
+        moves += 1;
+        println!("{moves:>5} {}", disk.layout());
+    });
+    println!(
+        "\nChecksum after {moves} moves with {policy:?}: {}",
+        compacted.checksum()
+    );
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::rng::Rng;
 
-    const INPUT: &'static str = "2333133121414131402";
+    const INPUT: &str = "2333133121414131402";
+
+    fn span(pos: u64, len: u64) -> Span {
+        Span { pos, len }
+    }
+
+    fn block_fragment(input: &str) -> u64 {
+        let mut disk: Vec<i64> = Vec::new();
+        for (i, c) in input.bytes().enumerate() {
+            let id = if i % 2 == 0 { i as i64 / 2 } else { -1 };
+            disk.extend(std::iter::repeat_n(id, usize::from(c - b'0')));
+        }
+        let (mut hole_idx, mut file_idx) = (0, disk.len());
+        loop {
+            while hole_idx < disk.len() && disk[hole_idx] >= 0 {
+                hole_idx += 1;
+            }
+            while file_idx > 0 && disk[file_idx - 1] < 0 {
+                file_idx -= 1;
+            }
+            if file_idx == 0 || hole_idx >= file_idx - 1 {
+                break;
+            }
+            disk.swap(hole_idx, file_idx - 1);
+        }
+        disk.iter()
+            .enumerate()
+            .filter(|(_, &block)| block >= 0)
+            .map(|(i, &block)| i as u64 * block as u64)
+            .sum()
+    }
+
+    fn linear_whole_file(disk: &Disk) -> u64 {
+        let mut files: Vec<Span> = disk.files.iter().map(|spans| spans[0]).collect();
+        let mut holes = disk.holes.clone();
+        for file in files.iter_mut().rev() {
+            for hole in &mut holes {
+                if hole.pos > file.pos {
+                    break;
+                }
+                if file.len <= hole.len {
+                    file.pos = hole.pos;
+                    hole.pos += file.len;
+                    hole.len -= file.len;
+                    break;
+                }
+            }
+        }
+        files
+            .iter()
+            .enumerate()
+            .map(|(id, file)| (file.pos..file.end()).sum::<u64>() * id as u64)
+            .sum()
+    }
 
     #[test]
     fn test_parse() {
-        let (disk, files, holes) = parse(INPUT);
-        assert_eq!(
-            disk.len(),
-            INPUT.bytes().map(|ch| (ch - b'0') as usize).sum()
-        );
+        let disk = parse(INPUT);
         assert_eq!(
-            disk,
-            vec![
-                0, 0, -1, -1, -1, 1, 1, 1, -1, -1, -1, 2, -1, -1, -1, 3, 3, 3, -1, 4, 4, -1, 5, 5,
-                5, 5, -1, 6, 6, 6, 6, -1, 7, 7, 7, -1, 8, 8, 8, 8, 9, 9
-            ]
+            disk.size,
+            INPUT.bytes().map(|ch| u64::from(ch - b'0')).sum()
         );
+        assert_eq!(disk.layout(), "00...111...2...333.44.5555.6666.777.888899");
         assert_eq!(
-            files,
-            vec![
+            disk.files,
+            [
                 (0, 2),
                 (5, 3),
                 (11, 1),
@@ -123,10 +331,11 @@ mod tests {
                 (36, 4),
                 (40, 2)
             ]
+            .map(|(pos, len)| vec![span(pos, len)])
         );
         assert_eq!(
-            holes,
-            vec![
+            disk.holes,
+            [
                 (2, 3),
                 (8, 3),
                 (12, 3),
@@ -137,20 +346,87 @@ mod tests {
                 (35, 1),
                 (40, 0)
             ]
+            .map(|(pos, len)| span(pos, len))
         )
     }
 
+    #[test]
+    fn test_compaction_layouts() {
+        let disk = parse(INPUT);
+        let mut layouts = Vec::new();
+        let fragmented = disk.compacted(Policy::Fragment, |disk| layouts.push(disk.layout()));
+        assert_eq!(layouts[0], "0099.111...2...333.44.5555.6666.777.8888..");
+        assert_eq!(
+            fragmented.layout(),
+            "0099811188827773336446555566.............."
+        );
+        assert_eq!(fragmented.files[8], vec![span(4, 1), span(8, 3)]);
+
+        layouts.clear();
+        let moved = disk.compacted(Policy::WholeFile, |disk| layouts.push(disk.layout()));
+        assert_eq!(
+            layouts,
+            vec![
+                "0099.111...2...333.44.5555.6666.777.8888..",
+                "0099.1117772...333.44.5555.6666.....8888..",
+                "0099.111777244.333....5555.6666.....8888..",
+                "00992111777.44.333....5555.6666.....8888.."
+            ]
+        );
+        assert_eq!(
+            moved.holes,
+            [(11, 1), (14, 1), (18, 4), (26, 1), (31, 5), (40, 2)].map(|(pos, len)| span(pos, len))
+        );
+        assert_eq!(fragmented.holes, vec![span(28, 14)]);
+    }
+
+    fn assert_matches_models(input: &str) {
+        let disk = parse(input);
+        assert_eq!(
+            disk.compacted(Policy::Fragment, |_| {}).checksum(),
+            block_fragment(input),
+            "{input}"
+        );
+        assert_eq!(
+            disk.compacted(Policy::WholeFile, |_| {}).checksum(),
+            linear_whole_file(&disk),
+            "{input}"
+        );
+    }
+
+    #[test]
+    fn test_matches_block_model() {
+        for input in ["0", "102", "0300412", INPUT] {
+            assert_matches_models(input);
+        }
+
+        let mut rng = Rng::new(0x0909_2024);
+        for _ in 0..500 {
+            let len = 1 + 2 * rng.below(20);
+            let input: String = (0..len)
+                .map(|_| char::from(b'0' + rng.below(10) as u8))
+                .collect();
+            assert_matches_models(&input);
+        }
+    }
+
     #[test]
     fn test_part1() {
-        let (disk, _, _) = parse(INPUT);
-        let checksum = part1(disk);
+        let disk = parse(INPUT);
+        let checksum = part1(&disk);
         assert_eq!(checksum, "1928");
     }
 
     #[test]
     fn test_part2() {
-        let (_, files, holes) = parse(INPUT);
-        let checksum = part2(files, holes);
+        let disk = parse(INPUT);
+        let checksum = part2(&disk);
         assert_eq!(checksum, "2858");
     }
+
+    #[test]
+    #[should_panic(expected = "Layout should only show files with single-digit base 36 ids")]
+    fn test_layout_rejects_ambiguous_ids() {
+        parse(&"1".repeat(73)).layout();
+    }
 }
@@ -78,7 +78,7 @@ mod tests {
     use super::*;
     use std::str;
 
-    const INPUT: &'static str =
+    const INPUT: &str =
         "89010123\n78121874\n87430965\n96549874\n45678903\n32019012\n01329801\n10456732";
 
     #[test]
@@ -1,5 +1,5 @@
 use super::DayResult;
-use crate::utils::bench::time_execution;
+use crate::utils::{bench::time_execution, scan};
 use std::{collections::HashMap, fs};
 
 pub fn run() -> DayResult {
@@ -18,15 +18,9 @@ pub fn run() -> DayResult {
 }
 
 fn parse(input: &str) -> Vec<u64> {
-    input
-        .trim()
-        .split(' ')
-        .map(|stone| {
-            stone
-                .parse()
-                .expect("Input should contain positive integers")
-        })
-        .collect()
+    scan::separated(input.trim().as_bytes(), b" ")
+        .collect::<Result<_, _>>()
+        .expect("Input should contain positive integers separated by spaces")
 }
 
 fn solve(stones: &[u64], blinks: u8) -> usize {
@@ -73,7 +67,7 @@ fn part2(stones: &[u64]) -> String {
 mod tests {
     use super::*;
 
-    const INPUT: &'static str = "125 17";
+    const INPUT: &str = "125 17";
 
     #[test]
     fn test_parse() {
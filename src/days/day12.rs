@@ -1,7 +1,7 @@
 use super::DayResult;
 use crate::utils::{
     bench::time_execution,
-    grid::{Coord, Grid},
+    grid::{BitGrid, Coord, Grid},
 };
 use std::{collections::HashSet, fs};
 
@@ -81,16 +81,18 @@ fn solve<F>(farm: &Grid<u8>, cost_metric: F) -> usize
 where
     F: Fn(&Grid<u8>, &HashSet<Coord>, u8) -> usize,
 {
-    let mut visited = HashSet::with_capacity(farm.height() * farm.width());
+    let mut visited = BitGrid::new(farm.height(), farm.width());
     farm.enumerate_cells()
         .filter_map(|(coord, &plant)| {
-            if visited.contains(&coord) {
+            if visited[coord] {
                 return None;
             }
             let region = dfs(farm, coord, plant);
             let area = region.len();
             let metric = cost_metric(farm, &region, plant);
-            visited.extend(region);
+            for coord in region {
+                visited.insert(coord);
+            }
             Some(area * metric)
         })
         .sum()
@@ -111,7 +113,7 @@ mod tests {
     use super::*;
     use std::str;
 
-    const INPUT: &'static str = "RRRRIICCFF\nRRRRIICCCF\nVVRRRCCFFF\nVVRCCCJFFF\nVVVVCJJCFE\nVVIVCCJJEE\nVVIIICJJEE\nMIIIIIJJEE\nMIIISIJEEE\nMMMISSJEEE";
+    const INPUT: &str = "RRRRIICCFF\nRRRRIICCCF\nVVRRRCCFFF\nVVRCCCJFFF\nVVVVCJJCFE\nVVIVCCJJEE\nVVIIICJJEE\nMIIIIIJJEE\nMIIISIJEEE\nMMMISSJEEE";
 
     #[test]
     fn test_parse() {
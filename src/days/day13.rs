@@ -1,5 +1,9 @@
 use super::DayResult;
-use crate::utils::{bench::time_execution, grid::Coord};
+use crate::utils::{
+    bench::time_execution,
+    grid::Coord,
+    parse::{normalise, Span},
+};
 use std::fs;
 
 pub fn run() -> DayResult {
@@ -20,20 +24,15 @@ pub fn run() -> DayResult {
 type Pair = Coord;
 
 fn parse(input: &str) -> Vec<Pair> {
-    input
+    let input = normalise(input);
+    Span::new(&input)
         .lines()
-        .filter(|line| line.len() > 1)
+        .filter(|line| !line.trim().is_empty())
         .map(|line| {
-            let (_, right) = line
-                .split_once(": ")
-                .expect("Label and values should be colon separated");
-            let (x, y) = right
-                .split_once(", ")
-                .expect("X and Y components should be comma separated");
-            Pair::new(
-                y[2..].parse().expect("Y component should be an integer"),
-                x[2..].parse().expect("X component should be an integer"),
-            )
+            let [x, y] = line
+                .integers_array()
+                .expect("Each line should have an X and a Y component");
+            Pair::new(y, x)
         })
         .collect()
 }
@@ -75,7 +74,7 @@ fn part2(vals: &[Pair]) -> String {
 mod tests {
     use super::*;
 
-    const INPUT: &'static str = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\nButton A: X+26, Y+66\nButton B: X+67, Y+21\nPrize: X=12748, Y=12176\n\nButton A: X+17, Y+86\nButton B: X+84, Y+37\nPrize: X=7870, Y=6450\n\nButton A: X+69, Y+23\nButton B: X+27, Y+71\nPrize: X=18641, Y=10279";
+    const INPUT: &str = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\nButton A: X+26, Y+66\nButton B: X+67, Y+21\nPrize: X=12748, Y=12176\n\nButton A: X+17, Y+86\nButton B: X+84, Y+37\nPrize: X=7870, Y=6450\n\nButton A: X+69, Y+23\nButton B: X+27, Y+71\nPrize: X=18641, Y=10279";
 
     #[test]
     fn test_parse() {
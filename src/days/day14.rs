@@ -1,17 +1,22 @@
 use super::DayResult;
 use crate::utils::{
     bench::time_execution,
-    grid::{Coord, Grid},
+    cycle::{self, Cycle},
+    grid::{Action, Agent, BitGrid, Coord, Edges, FrameWriter, Grid, Scalar, Simulation},
+    parse::{normalise, Span},
 };
-use std::fs;
+use std::{fs, io};
+
+const LOBBY_SIZE: (usize, usize) = (103, 101);
 
 pub fn run() -> DayResult {
     let input = fs::read_to_string("inputs/14.in").expect("Input file should be readable");
 
     let parsed = time_execution(|| parse(&input));
     let robots = parsed.result;
-    let part1 = time_execution(|| part1(&robots, 100, 103, 101));
-    let part2 = time_execution(|| part2(&robots, 103, 101));
+    let (height, width) = LOBBY_SIZE;
+    let part1 = time_execution(|| part1(&robots, 100, height, width));
+    let part2 = time_execution(|| part2(&robots, height, width));
 
     DayResult {
         parse_duration: parsed.duration,
@@ -20,47 +25,31 @@ pub fn run() -> DayResult {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Robot {
-    pub pos: Coord,
-    pub vel: Coord,
-}
-
-fn parse(input: &str) -> Vec<Robot> {
-    input
+fn parse(input: &str) -> Vec<Agent> {
+    let input = normalise(input);
+    Span::new(&input)
         .lines()
         .map(|line| {
-            let (pos, vel) = line
-                .split_once(' ')
-                .expect("Position and Velocity should be separated by a space");
-            let (pos_x, pos_y) = pos
-                .split_once(',')
-                .expect("Coordinates should have comma separated components");
-            let pos = Coord::new(
-                pos_y.parse().expect("Y Position should be an integer"),
-                pos_x[2..].parse().expect("X Position should be an integer"),
-            );
-            let (vel_x, vel_y) = vel
-                .split_once(',')
-                .expect("Coordinates should have comma separated components");
-            let vel = Coord::new(
-                vel_y.parse().expect("Y Velocity should be an integer"),
-                vel_x[2..].parse().expect("X Velocity should be an integer"),
-            );
-            Robot { pos, vel }
+            let [pos_x, pos_y, vel_x, vel_y] = line
+                .integers_array()
+                .expect("Each robot should have a position and a velocity");
+            Agent::moving(Coord::new(pos_y, pos_x), Coord::new(vel_y, vel_x))
         })
         .collect()
 }
 
-fn part1(robots: &[Robot], time: usize, height: usize, width: usize) -> String {
-    let (time, height, width) = (time as isize, height as isize, width as isize);
-    let (half_height, half_width) = (height / 2, width / 2);
+fn lobby(robots: &[Agent], height: usize, width: usize) -> Simulation<()> {
+    Simulation::new(Grid::new(height, width, ()), robots.to_vec(), Edges::Wrap)
+}
+
+fn part1(robots: &[Agent], time: usize, height: usize, width: usize) -> String {
+    let mut lobby = lobby(robots, height, width);
+    lobby.advance_by(time);
+    let (half_height, half_width) = ((height / 2) as isize, (width / 2) as isize);
     let (mut top_left, mut top_right, mut bot_left, mut bot_right) = (0, 0, 0, 0);
 
-    for robot in robots {
-        let new_pos = robot.pos + robot.vel * time;
-        let final_x = new_pos.x().rem_euclid(width);
-        let final_y = new_pos.y().rem_euclid(height);
+    for robot in lobby.active_agents() {
+        let (final_x, final_y) = (robot.pos.x(), robot.pos.y());
         if final_x == half_width || final_y == half_height {
             continue;
         }
@@ -81,33 +70,87 @@ fn part1(robots: &[Robot], time: usize, height: usize, width: usize) -> String {
     format!("{safety_factor}")
 }
 
-fn part2(robots: &[Robot], height: usize, width: usize) -> String {
-    let (height, width) = (height as isize, width as isize);
-    let mut elapsed = 0;
-    let mut seen = Grid::new(height as usize, width as usize, false);
-
-    'a: loop {
-        for robot in robots {
-            let new_pos = robot.pos + robot.vel * elapsed;
-            let final_x = new_pos.x().rem_euclid(width);
-            let final_y = new_pos.y().rem_euclid(height);
-            let final_pos = Coord::new(final_y, final_x);
-            if seen[final_pos] {
-                elapsed += 1;
-                seen.fill(false);
-                continue 'a;
-            }
-            seen[final_pos] = true;
+fn axis_cycle(robots: &[Agent], axis: fn(Coord) -> isize, size: usize) -> Cycle {
+    let size = isize::try_from(size).expect("Lobby size should fit in isize");
+    let velocities: Vec<isize> = robots.iter().map(|robot| axis(robot.vel)).collect();
+    let positions: Vec<isize> = robots.iter().map(|robot| axis(robot.pos)).collect();
+    cycle::floyd(&positions, |positions| {
+        let moved = positions.iter().zip(&velocities);
+        Some(
+            moved
+                .map(|(pos, vel)| (pos + vel).rem_euclid(size))
+                .collect(),
+        )
+    })
+    .expect("Robots in a wrapping lobby should always cycle")
+}
+
+fn lobby_cycle(robots: &[Agent], height: usize, width: usize) -> Cycle {
+    let rows = axis_cycle(robots, |coord| coord.r, height);
+    let cols = axis_cycle(robots, |coord| coord.c, width);
+    let gcd = (rows.length as isize).gcd(cols.length as isize) as usize;
+    Cycle {
+        start: rows.start.max(cols.start),
+        length: rows.length / gcd * cols.length,
+    }
+}
+
+fn find_tree(robots: &[Agent], height: usize, width: usize) -> Option<usize> {
+    let period = lobby_cycle(robots, height, width).length;
+    let mut lobby = lobby(robots, height, width);
+    let mut seen = BitGrid::new(height, width);
+    while lobby.has_collisions(&mut seen) {
+        lobby.step(|_, _| Action::Advance);
+        if lobby.tick() == period {
+            return None;
+        }
+    }
+    Some(lobby.tick())
+}
+
+fn part2(robots: &[Agent], height: usize, width: usize) -> String {
+    match find_tree(robots, height, width) {
+        Some(elapsed) => format!("{elapsed}"),
+        None => "no tree".to_owned(),
+    }
+}
+
+pub fn export_frames(dir: &str, from: Option<usize>, count: Option<usize>) -> io::Result<()> {
+    let input = fs::read_to_string("inputs/14.in")?;
+    let robots = parse(&input);
+    let (height, width) = LOBBY_SIZE;
+    let lobby = lobby(&robots, height, width);
+    let mut lobby = match from {
+        Some(from) => lobby_cycle(&robots, height, width)
+            .fast_forward(
+                &lobby,
+                |lobby| {
+                    let mut next = lobby.clone();
+                    next.step(|_, _| Action::Advance);
+                    Some(next)
+                },
+                from,
+            )
+            .expect("Lobby should always step"),
+        None => {
+            let mut lobby = lobby;
+            lobby.advance_by(find_tree(&robots, height, width).unwrap_or_default());
+            lobby
         }
-        return format!("{elapsed}");
+    };
+    let mut frames = FrameWriter::new(dir, "day14")?;
+    for _ in 0..count.unwrap_or(1) {
+        frames.write_pbm(&lobby.occupancy(), |&robots| robots > 0)?;
+        lobby.step(|_, _| Action::Advance);
     }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &'static str = "p=0,4 v=3,-3\np=6,3 v=-1,-3\np=10,3 v=-1,2\np=2,0 v=2,-1\np=0,0 v=1,3\np=3,0 v=-2,-2\np=7,6 v=-1,-3\np=3,0 v=-1,-2\np=9,3 v=2,3\np=7,3 v=-1,2\np=2,4 v=2,-3\np=9,5 v=-3,-3\n";
+    const INPUT: &str = "p=0,4 v=3,-3\np=6,3 v=-1,-3\np=10,3 v=-1,2\np=2,0 v=2,-1\np=0,0 v=1,3\np=3,0 v=-2,-2\np=7,6 v=-1,-3\np=3,0 v=-1,-2\np=9,3 v=2,3\np=7,3 v=-1,2\np=2,4 v=2,-3\np=9,5 v=-3,-3\n";
 
     #[test]
     fn test_parse() {
@@ -116,54 +159,18 @@ mod tests {
         assert_eq!(
             robots,
             vec![
-                Robot {
-                    pos: Coord::new(4, 0),
-                    vel: Coord::new(-3, 3)
-                },
-                Robot {
-                    pos: Coord::new(3, 6),
-                    vel: Coord::new(-3, -1)
-                },
-                Robot {
-                    pos: Coord::new(3, 10),
-                    vel: Coord::new(2, -1)
-                },
-                Robot {
-                    pos: Coord::new(0, 2),
-                    vel: Coord::new(-1, 2)
-                },
-                Robot {
-                    pos: Coord::new(0, 0),
-                    vel: Coord::new(3, 1)
-                },
-                Robot {
-                    pos: Coord::new(0, 3),
-                    vel: Coord::new(-2, -2)
-                },
-                Robot {
-                    pos: Coord::new(6, 7),
-                    vel: Coord::new(-3, -1)
-                },
-                Robot {
-                    pos: Coord::new(0, 3),
-                    vel: Coord::new(-2, -1)
-                },
-                Robot {
-                    pos: Coord::new(3, 9),
-                    vel: Coord::new(3, 2)
-                },
-                Robot {
-                    pos: Coord::new(3, 7),
-                    vel: Coord::new(2, -1)
-                },
-                Robot {
-                    pos: Coord::new(4, 2),
-                    vel: Coord::new(-3, 2)
-                },
-                Robot {
-                    pos: Coord::new(5, 9),
-                    vel: Coord::new(-3, -3)
-                },
+                Agent::moving(Coord::new(4, 0), Coord::new(-3, 3)),
+                Agent::moving(Coord::new(3, 6), Coord::new(-3, -1)),
+                Agent::moving(Coord::new(3, 10), Coord::new(2, -1)),
+                Agent::moving(Coord::new(0, 2), Coord::new(-1, 2)),
+                Agent::moving(Coord::new(0, 0), Coord::new(3, 1)),
+                Agent::moving(Coord::new(0, 3), Coord::new(-2, -2)),
+                Agent::moving(Coord::new(6, 7), Coord::new(-3, -1)),
+                Agent::moving(Coord::new(0, 3), Coord::new(-2, -1)),
+                Agent::moving(Coord::new(3, 9), Coord::new(3, 2)),
+                Agent::moving(Coord::new(3, 7), Coord::new(2, -1)),
+                Agent::moving(Coord::new(4, 2), Coord::new(-3, 2)),
+                Agent::moving(Coord::new(5, 9), Coord::new(-3, -3)),
             ]
         );
     }
@@ -181,4 +188,21 @@ mod tests {
         let tree_time = part2(&robots, 7, 11);
         assert_eq!(tree_time, "1");
     }
+
+    #[test]
+    fn test_lobby_cycle() {
+        let robots = parse(INPUT);
+        let cycle = lobby_cycle(&robots, 7, 11);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 0,
+                length: 77
+            }
+        );
+
+        let stacked = [Agent::moving(Coord::new(1, 1), Coord::new(0, 0)); 2];
+        assert_eq!(lobby_cycle(&stacked, 7, 11).length, 1);
+        assert_eq!(part2(&stacked, 7, 11), "no tree");
+    }
 }
@@ -2,12 +2,13 @@ use super::DayResult;
 use crate::utils::{
     bench::time_execution,
     grid::{
-        Coord,
+        Action, Agent, Coord,
         Direction::{self, *},
-        Grid, Robot,
+        Edges, FrameWriter, Grid, Recording, Rgb, Simulation,
     },
+    parse::{normalise, Span},
 };
-use std::{collections::VecDeque, fs};
+use std::{collections::VecDeque, fs, io, time::Duration};
 
 pub fn run() -> DayResult {
     let input = fs::read_to_string("inputs/15.in").expect("Input file should be readable");
@@ -25,174 +26,185 @@ pub fn run() -> DayResult {
 }
 
 fn parse(input: &str) -> (Grid<u8>, Grid<u8>, Vec<Direction>) {
-    let input = input.trim().replace('\r', "");
-    let (warehouse, movements) = input
-        .split_once("\n\n")
+    let input = normalise(input);
+    let [warehouse, movements] = Span::new(&input)
+        .sections_array()
         .expect("Warehouse and movements should be separated by a blank line");
 
-    let grid = Grid::from(warehouse);
+    let grid = Grid::from(warehouse.as_str());
 
     let expanded_warehouse = warehouse
+        .as_str()
         .replace('#', "##")
         .replace('.', "..")
         .replace('O', "[]")
         .replace('@', "@.");
     let expanded_grid = Grid::from(expanded_warehouse.as_str());
 
-    let movements = movements.lines().collect::<Vec<_>>().join("");
     let movements = movements
-        .bytes()
-        .map(|movement| match movement {
-            b'^' => North,
-            b'>' => East,
-            b'v' => South,
-            b'<' => West,
-            _ => unreachable!("Movements should only consist of ^><v"),
+        .trim()
+        .lines()
+        .flat_map(|line| line.as_str().bytes())
+        .map(|movement| {
+            Direction::try_from(movement).expect("Movements should only consist of ^><v")
         })
         .collect();
 
     (grid, expanded_grid, movements)
 }
 
-fn go(warehouse: &mut Grid<u8>, robot: &mut Robot) {
-    warehouse[robot.position()] = b'.';
-    warehouse[robot.next_pos()] = b'@';
-    robot.locomote();
+fn go(warehouse: &mut Grid<u8>, robot: &Agent, movement: Direction) -> Action {
+    warehouse[robot.pos] = b'.';
+    warehouse[robot.pos + movement] = b'@';
+    Action::Step(movement)
 }
 
-fn part1(mut warehouse: Grid<u8>, movements: &[Direction]) -> String {
-    let start = warehouse
-        .position(b'@')
-        .expect("Warehouse should contain robot");
-    let mut robot = Robot::new(start, North);
-
-    for &movement in movements {
-        robot.dir = movement;
-        let next_pos = robot.next_pos();
+fn step(warehouse: &mut Grid<u8>, robot: &Agent, movement: Direction) -> Action {
+    let next_pos = robot.pos + movement;
 
-        match warehouse[next_pos] {
-            b'#' => (),
-            b'.' => {
-                go(&mut warehouse, &mut robot);
+    match warehouse[next_pos] {
+        b'#' => Action::Wait,
+        b'.' => go(warehouse, robot, movement),
+        b'O' => {
+            let mut fut_pos = next_pos + movement;
+            while warehouse[fut_pos] == b'O' {
+                fut_pos += movement;
             }
-            b'O' => {
-                let mut fut_pos = next_pos + movement;
-                while warehouse[fut_pos] == b'O' {
-                    fut_pos += movement;
-                }
-                if warehouse[fut_pos] != b'#' {
-                    warehouse[fut_pos] = b'O';
-                    go(&mut warehouse, &mut robot);
-                }
+            if warehouse[fut_pos] == b'#' {
+                return Action::Wait;
             }
-            _ => unreachable!(),
+            warehouse[fut_pos] = b'O';
+            go(warehouse, robot, movement)
         }
+        _ => unreachable!(),
     }
-
-    let total_gps_coordinates: isize = warehouse
-        .positions(b'O')
-        .map(|coord| coord.r * 100 + coord.c)
-        .sum();
-    format!("{total_gps_coordinates}")
 }
 
-fn part2(mut warehouse: Grid<u8>, movements: &[Direction]) -> String {
-    let start = warehouse
-        .position(b'@')
-        .expect("Warehouse should contain robot");
-    let mut robot = Robot::new(start, North);
+fn step_wide(warehouse: &mut Grid<u8>, robot: &Agent, movement: Direction) -> Action {
+    let next_pos = robot.pos + movement;
 
-    'a: for &movement in movements {
-        robot.dir = movement;
-        let next_pos = robot.next_pos();
+    if warehouse[next_pos] == b'#' {
+        return Action::Wait;
+    }
+    if warehouse[next_pos] == b'.' {
+        return go(warehouse, robot, movement);
+    }
 
-        if warehouse[next_pos] == b'#' {
-            continue;
-        }
-        if warehouse[next_pos] == b'.' {
-            go(&mut warehouse, &mut robot);
-            continue;
-        }
+    let dir = Coord::from(movement);
+    match movement {
+        East | West => {
+            let (b1, b2) = if warehouse[next_pos] == b'[' {
+                (b'[', b']')
+            } else {
+                (b']', b'[')
+            };
+
+            let mut len = 0;
+            let mut fut_pos = next_pos;
+            while warehouse[fut_pos] == b1 {
+                fut_pos += dir * 2;
+                len += 2;
+            }
 
-        let dir = Coord::from(movement);
-        match movement {
-            East | West => {
-                let (b1, b2) = if warehouse[next_pos] == b'[' {
-                    (b'[', b']')
+            if warehouse[fut_pos] == b'#' {
+                return Action::Wait;
+            }
+            for i in 1..=len {
+                let pos = next_pos + dir * i;
+                if i % 2 == 1 {
+                    warehouse[pos] = b1;
                 } else {
-                    (b']', b'[')
-                };
-
-                let mut len = 0;
-                let mut fut_pos = next_pos;
-                while warehouse[fut_pos] == b1 {
-                    fut_pos += dir * 2;
-                    len += 2;
-                }
-
-                if warehouse[fut_pos] != b'#' {
-                    for i in 1..=len {
-                        let pos = next_pos + dir * i;
-                        if i % 2 == 1 {
-                            warehouse[pos] = b1;
-                        } else {
-                            warehouse[pos] = b2;
-                        }
-                    }
-                    go(&mut warehouse, &mut robot);
+                    warehouse[pos] = b2;
                 }
             }
-            North | South => {
-                let mut queue = VecDeque::new();
-                let mut seen = Vec::new();
-                let adj_pos = if warehouse[next_pos] == b'[' {
-                    next_pos + East
+            go(warehouse, robot, movement)
+        }
+        North | South => {
+            let mut queue = VecDeque::new();
+            let mut seen = Vec::new();
+            let adj_pos = if warehouse[next_pos] == b'[' {
+                next_pos + East
+            } else {
+                next_pos + West
+            };
+            queue.push_back(next_pos);
+            queue.push_back(adj_pos);
+            seen.push(next_pos);
+            seen.push(adj_pos);
+
+            while let Some(coord) = queue.pop_front() {
+                let new_pos = coord + dir;
+
+                if warehouse[new_pos] == b'.' {
+                    continue;
+                }
+                if warehouse[new_pos] == b'#' {
+                    return Action::Wait;
+                }
+
+                let adj_pos = if warehouse[new_pos] == b'[' {
+                    new_pos + East
                 } else {
-                    next_pos + West
+                    new_pos + West
                 };
-                queue.push_back(next_pos);
-                queue.push_back(adj_pos);
-                seen.push(next_pos);
-                seen.push(adj_pos);
-
-                while let Some(coord) = queue.pop_front() {
-                    let new_pos = coord + dir;
-
-                    if warehouse[new_pos] == b'.' {
-                        continue;
-                    }
-                    if warehouse[new_pos] == b'#' {
-                        continue 'a;
-                    }
-
-                    let adj_pos = if warehouse[new_pos] == b'[' {
-                        new_pos + East
-                    } else {
-                        new_pos + West
-                    };
-
-                    if !seen.contains(&new_pos) {
-                        queue.push_back(new_pos);
-                        seen.push(new_pos);
-                    }
-                    if !seen.contains(&adj_pos) {
-                        queue.push_back(adj_pos);
-                        seen.push(adj_pos);
-                    }
-                }
 
-                for coord in seen.into_iter().rev() {
-                    let new_coord = coord + dir;
-                    warehouse[new_coord] = warehouse[coord];
-                    warehouse[coord] = b'.';
+                if !seen.contains(&new_pos) {
+                    queue.push_back(new_pos);
+                    seen.push(new_pos);
+                }
+                if !seen.contains(&adj_pos) {
+                    queue.push_back(adj_pos);
+                    seen.push(adj_pos);
                 }
+            }
 
-                go(&mut warehouse, &mut robot);
+            for coord in seen.into_iter().rev() {
+                let new_coord = coord + dir;
+                warehouse[new_coord] = warehouse[coord];
+                warehouse[coord] = b'.';
             }
-            _ => unreachable!(),
+
+            go(warehouse, robot, movement)
         }
+        _ => unreachable!(),
+    }
+}
+
+fn simulate<R, H>(warehouse: Grid<u8>, movements: &[Direction], rule: R, mut on_step: H) -> Grid<u8>
+where
+    R: Fn(&mut Grid<u8>, &Agent, Direction) -> Action,
+    H: FnMut(&Grid<u8>) -> bool,
+{
+    let start = warehouse
+        .position(b'@')
+        .expect("Warehouse should contain robot");
+    let mut movements = movements.iter();
+    let mut sim = Simulation::new(warehouse, vec![Agent::heading(start, North)], Edges::Block);
+
+    if on_step(sim.world()) {
+        sim.run(
+            |warehouse, robot| {
+                movements.next().map_or(Action::Despawn, |&movement| {
+                    rule(warehouse, robot, movement)
+                })
+            },
+            |sim| on_step(sim.world()),
+        );
     }
+    sim.into_world()
+}
+
+fn part1(warehouse: Grid<u8>, movements: &[Direction]) -> String {
+    let warehouse = simulate(warehouse, movements, step, |_| true);
+    let total_gps_coordinates: isize = warehouse
+        .positions(b'O')
+        .map(|coord| coord.r * 100 + coord.c)
+        .sum();
+    format!("{total_gps_coordinates}")
+}
 
+fn part2(warehouse: Grid<u8>, movements: &[Direction]) -> String {
+    let warehouse = simulate(warehouse, movements, step_wide, |_| true);
     let total_gps_coordinates: isize = warehouse
         .positions(b'[')
         .map(|coord| coord.r * 100 + coord.c)
@@ -200,13 +212,53 @@ fn part2(mut warehouse: Grid<u8>, movements: &[Direction]) -> String {
     format!("{total_gps_coordinates}")
 }
 
+fn tile_colour(&tile: &u8) -> Rgb {
+    match tile {
+        b'#' => (128, 128, 128),
+        b'O' | b'[' | b']' => (181, 101, 29),
+        b'@' => (220, 20, 60),
+        _ => (0, 0, 0),
+    }
+}
+
+pub fn export_frames(dir: &str, from: Option<usize>, count: Option<usize>) -> io::Result<()> {
+    let input = fs::read_to_string("inputs/15.in")?;
+    let (_, warehouse, movements) = parse(&input);
+    let from = from.unwrap_or(0);
+    let end = count.map_or(usize::MAX, |count| from + count);
+
+    let mut frames = FrameWriter::new(dir, "day15")?;
+    let mut result = Ok(());
+    let mut time = 0;
+    simulate(warehouse, &movements, step_wide, |warehouse| {
+        if time >= from {
+            result = frames.write_ppm(warehouse, tile_colour).map(|_| ());
+        }
+        time += 1;
+        result.is_ok() && time < end
+    });
+    result
+}
+
+pub fn record(path: &str) -> io::Result<()> {
+    let input = fs::read_to_string("inputs/15.in")?;
+    let (_, warehouse, movements) = parse(&input);
+
+    let mut recording = Recording::new(Duration::from_millis(20));
+    simulate(warehouse, &movements, step_wide, |warehouse| {
+        recording.capture(warehouse);
+        true
+    });
+    recording.save(path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::str;
 
-    const INPUT1: &'static str = "########\n#..O.O.#\n##@.O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########\n\n<^^>>>vv<v>>v<<";
-    const INPUT2: &'static str =
+    const INPUT1: &str = "########\n#..O.O.#\n##@.O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########\n\n<^^>>>vv<v>>v<<";
+    const INPUT2: &str =
         "#######\n#...#.#\n#.....#\n#..OO@#\n#..O..#\n#.....#\n#######\n\n<vv<<^^<<^^";
 
     #[test]
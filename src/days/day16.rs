@@ -2,9 +2,9 @@ use super::DayResult;
 use crate::utils::{
     bench::time_execution,
     grid::{
-        Coord,
+        Agent, Coord,
         Direction::{self, *},
-        Grid, Robot,
+        Grid,
     },
 };
 use std::{
@@ -32,7 +32,7 @@ fn parse(input: &str) -> Grid<u8> {
     Grid::from(input)
 }
 
-type Node = Robot;
+type Node = Agent;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct State {
@@ -43,8 +43,8 @@ struct State {
 impl State {
     pub fn next(mut self, degrees: i16) -> Self {
         self.cost += if degrees == 0 { 1 } else { 1001 };
-        self.node.rotate(degrees);
-        self.node.locomote();
+        let dir = self.node.dir.rotated(degrees);
+        self.node = Node::heading(self.node.pos + dir, dir);
         self
     }
 }
@@ -69,10 +69,7 @@ fn dijkstra(
     let mut costs = HashMap::with_capacity(1024);
     let mut prev = HashMap::with_capacity(1024);
     let mut pq = BinaryHeap::with_capacity(1024);
-    let node = Node {
-        pos: start,
-        dir: East,
-    };
+    let node = Node::heading(start, East);
     let state = State { cost: 0, node };
     costs.insert(node, 0);
     prev.insert(node, vec![]);
@@ -116,10 +113,7 @@ fn part2(maze: &Grid<u8>) -> String {
     let end = maze.position(b'E').expect("Maze should have an end");
     let (mut prev, _, last_dir) = dijkstra(maze, start, end);
     let mut seen = HashSet::with_capacity(768);
-    let mut stack = vec![Node {
-        pos: end,
-        dir: last_dir,
-    }];
+    let mut stack = vec![Node::heading(end, last_dir)];
 
     while let Some(node) = stack.pop() {
         seen.insert(node.pos);
@@ -138,7 +132,7 @@ mod tests {
     use super::*;
     use std::str;
 
-    const INPUT: &'static str = "###############\n#.......#....E#\n#.#.###.#.###.#\n#.....#.#...#.#\n#.###.#####.#.#\n#.#.#.......#.#\n#.#.#####.###.#\n#...........#.#\n###.#.#####.#.#\n#...#.....#.#.#\n#.#.#.###.#.#.#\n#.....#...#.#.#\n#.###.#.#.#.#.#\n#S..#.....#...#\n###############";
+    const INPUT: &str = "###############\n#.......#....E#\n#.#.###.#.###.#\n#.....#.#...#.#\n#.###.#####.#.#\n#.#.#.......#.#\n#.#.#####.###.#\n#...........#.#\n###.#.#####.#.#\n#...#.....#.#.#\n#.#.#.###.#.#.#\n#.....#...#.#.#\n#.###.#.#.#.#.#\n#S..#.....#...#\n###############";
 
     #[test]
     fn test_parse() {
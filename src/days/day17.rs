@@ -1,7 +1,10 @@
 use super::DayResult;
-use crate::utils::bench::time_execution;
+use crate::utils::{
+    bench::time_execution,
+    parse::{normalise, Span},
+};
 use itertools::Itertools;
-use std::fs;
+use std::{collections::HashMap, error::Error, fmt, fs, io, str::FromStr};
 
 pub fn run() -> DayResult {
     let input = fs::read_to_string("inputs/17.in").expect("Input file should be readable");
@@ -19,90 +22,509 @@ pub fn run() -> DayResult {
 }
 
 fn parse(input: &str) -> (u64, u64, u64, Vec<u64>) {
-    let lines: Vec<&str> = input.lines().collect();
-    let a = lines[0][12..]
-        .parse()
-        .expect("Register A value should be an integer");
-    let b = lines[1][12..]
-        .parse()
-        .expect("Register A value should be an integer");
-    let c = lines[2][12..]
-        .parse()
-        .expect("Register A value should be an integer");
-    let program = lines[4][9..]
-        .split(',')
-        .map(|n| n.parse().expect("Program should consist of integers"))
-        .collect();
+    let input = normalise(input);
+    let [registers, program] = Span::new(&input)
+        .sections_array()
+        .expect("Registers and program should be separated by a blank line");
+    let mut lines = registers.lines();
+    let [a, b, c] = ["Register A", "Register B", "Register C"].map(|key| {
+        lines
+            .next()
+            .ok_or_else(|| registers.error(format!("missing {key}")))
+            .and_then(|line| line.value_of(key)?.parse())
+            .expect("Register values should be integers")
+    });
+    let program = program
+        .value_of("Program")
+        .and_then(|program| program.list(",", |n| n.parse()))
+        .expect("Program should consist of integers");
     (a, b, c, program)
 }
 
-fn compute(mut a: u64, mut b: u64, mut c: u64, program: &[u64]) -> Vec<u64> {
-    let n = program.len() - 1;
-    let mut ip = 0;
-    let mut output = Vec::new();
-
-    while ip < n {
-        let opcode = program[ip];
-        let literal = program[ip + 1];
-        let combo = match literal {
-            literal @ 0..=3 => literal,
-            4 => a,
-            5 => b,
-            6 => c,
-            _ => unreachable!("Invalid literal operand"),
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Opcode {
+    Adv,
+    Bxl,
+    Bst,
+    Jnz,
+    Bxc,
+    Out,
+    Bdv,
+    Cdv,
+    Extension { code: u64, mnemonic: &'static str },
+}
+
+impl Opcode {
+    const ALL: [Opcode; 8] = [
+        Opcode::Adv,
+        Opcode::Bxl,
+        Opcode::Bst,
+        Opcode::Jnz,
+        Opcode::Bxc,
+        Opcode::Out,
+        Opcode::Bdv,
+        Opcode::Cdv,
+    ];
+
+    fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Adv => "adv",
+            Opcode::Bxl => "bxl",
+            Opcode::Bst => "bst",
+            Opcode::Jnz => "jnz",
+            Opcode::Bxc => "bxc",
+            Opcode::Out => "out",
+            Opcode::Bdv => "bdv",
+            Opcode::Cdv => "cdv",
+            Opcode::Extension { mnemonic, .. } => mnemonic,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combo {
+    Literal(u64),
+    A,
+    B,
+    C,
+    Reserved,
+}
+
+impl fmt::Display for Combo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Combo::Literal(value) => write!(f, "{value}"),
+            Combo::A => write!(f, "a"),
+            Combo::B => write!(f, "b"),
+            Combo::C => write!(f, "c"),
+            Combo::Reserved => write!(f, "?"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Instruction {
+    opcode: Opcode,
+    operand: u64,
+}
+
+impl Instruction {
+    fn decode(opcode: u64, operand: u64) -> Option<Self> {
+        let opcode = *Opcode::ALL.get(usize::try_from(opcode).ok()?)?;
+        Some(Self { opcode, operand })
+    }
+
+    fn combo(self) -> Combo {
+        match self.operand {
+            literal @ 0..=3 => Combo::Literal(literal),
+            4 => Combo::A,
+            5 => Combo::B,
+            6 => Combo::C,
+            _ => Combo::Reserved,
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mnemonic = self.opcode.mnemonic();
+        let text = match self.opcode {
+            Opcode::Bxl | Opcode::Jnz | Opcode::Extension { .. } => {
+                format!("{mnemonic} {}", self.operand)
+            }
+            Opcode::Bxc => mnemonic.to_string(),
+            _ => format!("{mnemonic} {}", self.combo()),
         };
+        f.pad(&text)
+    }
+}
 
-        match opcode {
-            0 => a >>= combo,
-            1 => b ^= literal,
-            2 => b = combo % 8,
-            3 => {
-                if a != 0 {
-                    ip = literal as usize;
-                    continue;
-                }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Registers {
+    a: u64,
+    b: u64,
+    c: u64,
+}
+
+impl Registers {
+    fn resolve(&self, combo: Combo) -> Option<u64> {
+        match combo {
+            Combo::Literal(value) => Some(value),
+            Combo::A => Some(self.a),
+            Combo::B => Some(self.b),
+            Combo::C => Some(self.c),
+            Combo::Reserved => None,
+        }
+    }
+}
+
+impl fmt::Display for Registers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a={:<#18o} b={:<#18o} c={:<#18o}",
+            self.a, self.b, self.c
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum VmError {
+    InvalidOpcode { ip: usize, opcode: u64 },
+    ReservedOperand { ip: usize },
+    BudgetExhausted { budget: usize },
+    ReservedOpcode { code: u64 },
+}
+
+impl fmt::Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VmError::InvalidOpcode { ip, opcode } => {
+                write!(f, "Invalid opcode {opcode} at {ip:02}")
+            }
+            VmError::ReservedOperand { ip } => write!(f, "Reserved combo operand 7 at {ip:02}"),
+            VmError::BudgetExhausted { budget } => {
+                write!(f, "Instruction budget of {budget} exhausted")
+            }
+            VmError::ReservedOpcode { code } => {
+                write!(f, "Opcode {code} is reserved for a built-in instruction")
             }
-            4 => b ^= c,
-            5 => output.push(combo % 8),
-            6 => b = a >> combo,
-            7 => c = a >> combo,
-            _ => unreachable!("Invalid opcode"),
         }
+    }
+}
+
+impl Error for VmError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Effect {
+    Continue,
+    Jump(usize),
+    Output(u64),
+}
+
+type Handler = fn(&mut Registers, u64) -> Effect;
 
-        ip += 2;
+const EXTENSIONS: [(&str, Handler); 4] = [
+    ("add", |registers, operand| {
+        registers.a = registers.a.wrapping_add(operand);
+        Effect::Continue
+    }),
+    ("mul", |registers, operand| {
+        registers.a = registers.a.wrapping_mul(operand);
+        Effect::Continue
+    }),
+    ("swp", |registers, _| {
+        std::mem::swap(&mut registers.a, &mut registers.b);
+        Effect::Continue
+    }),
+    ("jmp", |_, operand| Effect::Jump(operand as usize)),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extensions(Vec<(u64, &'static str, Handler)>);
+
+impl FromStr for Extensions {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|extension| {
+                let (code, name) = extension
+                    .split_once('=')
+                    .ok_or_else(|| format!("Extension {extension} should be code=mnemonic"))?;
+                let code = code
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Invalid opcode {code}"))?;
+                let &(mnemonic, handler) = EXTENSIONS
+                    .iter()
+                    .find(|(mnemonic, _)| *mnemonic == name.trim())
+                    .ok_or_else(|| format!("Unknown extension {name}"))?;
+                Ok((code, mnemonic, handler))
+            })
+            .collect::<Result<_, _>>()
+            .map(Extensions)
     }
-    output
 }
 
-fn part1(a: u64, b: u64, c: u64, program: &[u64]) -> String {
-    let output = compute(a, b, c, program);
-    output.iter().join(",")
+struct Vm<'a> {
+    program: &'a [u64],
+    budget: Option<usize>,
+    extensions: HashMap<u64, (&'static str, Handler)>,
 }
 
-fn part2(program: &[u64]) -> String {
-    let mut a = 1;
-    let mut index = program.len() - 1;
-    loop {
-        if compute(a, 0, 0, program) == program[index..] {
-            if index == 0 {
-                return format!("{a}");
+impl<'a> Vm<'a> {
+    fn new(program: &'a [u64]) -> Self {
+        Self {
+            program,
+            budget: None,
+            extensions: HashMap::new(),
+        }
+    }
+
+    fn with_budget(mut self, budget: usize) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    fn with_opcode(
+        mut self,
+        code: u64,
+        mnemonic: &'static str,
+        handler: Handler,
+    ) -> Result<Self, VmError> {
+        if code < Opcode::ALL.len() as u64 {
+            return Err(VmError::ReservedOpcode { code });
+        }
+        self.extensions.insert(code, (mnemonic, handler));
+        Ok(self)
+    }
+
+    fn with_extensions(self, extensions: &Extensions) -> Result<Self, VmError> {
+        extensions
+            .0
+            .iter()
+            .try_fold(self, |vm, &(code, mnemonic, handler)| {
+                vm.with_opcode(code, mnemonic, handler)
+            })
+    }
+
+    fn decode(&self, ip: usize) -> Result<Instruction, VmError> {
+        let (opcode, operand) = (self.program[ip], self.program[ip + 1]);
+        Instruction::decode(opcode, operand)
+            .or_else(|| {
+                self.extensions
+                    .get(&opcode)
+                    .map(|&(mnemonic, _)| Instruction {
+                        opcode: Opcode::Extension {
+                            code: opcode,
+                            mnemonic,
+                        },
+                        operand,
+                    })
+            })
+            .ok_or(VmError::InvalidOpcode { ip, opcode })
+    }
+
+    fn disassemble(&self) -> String {
+        (0..self.program.len() / 2)
+            .map(|i| match self.decode(2 * i) {
+                Ok(instruction) => format!("{:02}: {instruction}", 2 * i),
+                Err(_) => format!("{:02}: ??? {}", 2 * i, self.program[2 * i + 1]),
+            })
+            .join("\n")
+    }
+
+    fn run<F>(&self, mut registers: Registers, mut on_step: F) -> Result<Vec<u64>, VmError>
+    where
+        F: FnMut(usize, Instruction, &Registers, &[u64]),
+    {
+        let divide = |a: u64, shift: u64| {
+            u32::try_from(shift)
+                .ok()
+                .and_then(|shift| a.checked_shr(shift))
+                .unwrap_or(0)
+        };
+        let mut ip = 0;
+        let mut steps = 0;
+        let mut output = Vec::new();
+
+        while ip + 1 < self.program.len() {
+            if self.budget.is_some_and(|budget| steps == budget) {
+                return Err(VmError::BudgetExhausted { budget: steps });
             }
-            a *= 8;
-            index -= 1;
-        } else {
-            a += 1;
+            steps += 1;
+
+            let instruction = self.decode(ip)?;
+            let operand = instruction.operand;
+            let combo = registers.resolve(instruction.combo());
+            let combo = || combo.ok_or(VmError::ReservedOperand { ip });
+
+            let effect = match instruction.opcode {
+                Opcode::Adv => {
+                    registers.a = divide(registers.a, combo()?);
+                    Effect::Continue
+                }
+                Opcode::Bxl => {
+                    registers.b ^= operand;
+                    Effect::Continue
+                }
+                Opcode::Bst => {
+                    registers.b = combo()? % 8;
+                    Effect::Continue
+                }
+                Opcode::Jnz if registers.a != 0 => Effect::Jump(operand as usize),
+                Opcode::Jnz => Effect::Continue,
+                Opcode::Bxc => {
+                    registers.b ^= registers.c;
+                    Effect::Continue
+                }
+                Opcode::Out => Effect::Output(combo()? % 8),
+                Opcode::Bdv => {
+                    registers.b = divide(registers.a, combo()?);
+                    Effect::Continue
+                }
+                Opcode::Cdv => {
+                    registers.c = divide(registers.a, combo()?);
+                    Effect::Continue
+                }
+                Opcode::Extension { code, .. } => {
+                    (self.extensions[&code].1)(&mut registers, operand)
+                }
+            };
+
+            let next_ip = match effect {
+                Effect::Continue => ip + 2,
+                Effect::Jump(target) => target,
+                Effect::Output(value) => {
+                    output.push(value);
+                    ip + 2
+                }
+            };
+            on_step(ip, instruction, &registers, &output);
+            ip = next_ip;
         }
+        Ok(output)
+    }
+}
+
+const INSTRUCTION_BUDGET: usize = 1 << 24;
+
+fn compute(a: u64, b: u64, c: u64, program: &[u64]) -> Result<Vec<u64>, VmError> {
+    Vm::new(program)
+        .with_budget(INSTRUCTION_BUDGET)
+        .run(Registers { a, b, c }, |_, _, _, _| {})
+}
+
+fn part1(a: u64, b: u64, c: u64, program: &[u64]) -> String {
+    match compute(a, b, c, program) {
+        Ok(output) => output.iter().join(","),
+        Err(err) => format!("error: {err}"),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LoopShape {
+    shift: u32,
+}
+
+fn analyse(program: &[u64]) -> Result<LoopShape, String> {
+    if program.is_empty() || !program.len().is_multiple_of(2) {
+        return Err("Program should consist of opcode and operand pairs".to_string());
+    }
+    let instructions: Vec<_> = program
+        .chunks_exact(2)
+        .map(|pair| {
+            Instruction::decode(pair[0], pair[1]).ok_or(format!("Invalid opcode {}", pair[0]))
+        })
+        .collect::<Result<_, _>>()?;
+    let (last, body) = instructions
+        .split_last()
+        .expect("Program should not be empty");
+    let count = |opcode| body.iter().filter(|i| i.opcode == opcode).count();
+
+    if last.opcode != Opcode::Jnz || last.operand != 0 || count(Opcode::Jnz) > 0 {
+        return Err("Program should be a single loop ending in jnz 0".to_string());
+    }
+    if count(Opcode::Out) != 1 {
+        return Err("Loop should output exactly one value per iteration".to_string());
+    }
+    let shifts: Vec<_> = body.iter().filter(|i| i.opcode == Opcode::Adv).collect();
+    let shift = match shifts[..] {
+        [shift] => match shift.combo() {
+            Combo::Literal(bits @ 1..=3) => bits as u32,
+            _ => return Err("A should be shifted by a constant each iteration".to_string()),
+        },
+        _ => return Err("Loop should shift A exactly once per iteration".to_string()),
+    };
+
+    let (mut b_written, mut c_written) = (false, false);
+    for instruction in body {
+        let combo = instruction.combo();
+        let reads_b = matches!(instruction.opcode, Opcode::Bxl | Opcode::Bxc)
+            || (instruction.opcode != Opcode::Bxl && combo == Combo::B);
+        let reads_c = instruction.opcode == Opcode::Bxc
+            || (!matches!(instruction.opcode, Opcode::Bxl | Opcode::Bxc) && combo == Combo::C);
+        if (reads_b && !b_written) || (reads_c && !c_written) {
+            return Err("Loop should not depend on B or C from the previous iteration".to_string());
+        }
+        match instruction.opcode {
+            Opcode::Bxl | Opcode::Bst | Opcode::Bxc | Opcode::Bdv => b_written = true,
+            Opcode::Cdv => c_written = true,
+            _ => {}
+        }
+    }
+
+    Ok(LoopShape { shift })
+}
+
+fn find_quine(program: &[u64]) -> Result<u64, String> {
+    fn search(program: &[u64], shift: u32, index: usize, a: u64) -> Option<u64> {
+        if a.leading_zeros() < shift {
+            return None;
+        }
+        (0..1 << shift).find_map(|digit| {
+            let candidate = (a << shift) | digit;
+            if compute(candidate, 0, 0, program).as_deref() != Ok(&program[index..]) {
+                None
+            } else if index == 0 {
+                Some(candidate)
+            } else {
+                search(program, shift, index - 1, candidate)
+            }
+        })
+    }
+
+    let shape = analyse(program)?;
+    search(program, shape.shift, program.len() - 1, 0)
+        .ok_or_else(|| "No value of A makes the program output itself".to_string())
+}
+
+fn part2(program: &[u64]) -> String {
+    match find_quine(program) {
+        Ok(a) => format!("{a}"),
+        Err(err) => format!("unsupported: {err}"),
     }
 }
 
+pub fn trace(budget: Option<usize>, extensions: Option<Extensions>) -> io::Result<()> {
+    let input = fs::read_to_string("inputs/17.in")?;
+    let (a, b, c, program) = parse(&input);
+    let mut vm = Vm::new(&program);
+    if let Some(extensions) = extensions {
+        vm = vm.with_extensions(&extensions).map_err(io::Error::other)?;
+    }
+    if let Some(budget) = budget {
+        vm = vm.with_budget(budget);
+    }
+
+    println!("{}\n", vm.disassemble());
+    let mut step = 0;
+    let output = vm
+        .run(
+            Registers { a, b, c },
+            |ip, instruction, registers, output| {
+                step += 1;
+                let printed = match instruction.opcode {
+                    Opcode::Out => format!("-> {}", output[output.len() - 1]),
+                    _ => String::new(),
+                };
+                let line = format!("{step:>6} {ip:02}: {instruction:<6} {registers} {printed}");
+                println!("{}", line.trim_end());
+            },
+        )
+        .map_err(io::Error::other)?;
+    println!("\nOutput: {}", output.iter().join(","));
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT1: &'static str =
-        "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0";
-    const INPUT2: &'static str =
-        "Register A: 2024\nRegister B: 0\nRegister C: 0\n\nProgram: 0,3,5,4,3,0";
+    const INPUT1: &str = "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0";
+    const INPUT2: &str = "Register A: 2024\nRegister B: 0\nRegister C: 0\n\nProgram: 0,3,5,4,3,0";
 
     #[test]
     fn test_parse() {
@@ -114,6 +536,106 @@ mod tests {
         assert_eq!(program, vec![0, 1, 5, 4, 3, 0]);
     }
 
+    #[test]
+    fn test_disassemble() {
+        let (_, _, _, program) = parse(INPUT1);
+        assert_eq!(
+            Vm::new(&program).disassemble(),
+            "00: adv 1\n02: out a\n04: jnz 0"
+        );
+        let program = [2, 4, 1, 5, 7, 5, 4, 3, 6, 6, 0, 3];
+        assert_eq!(
+            Vm::new(&program).disassemble(),
+            "00: bst a\n02: bxl 5\n04: cdv b\n06: bxc\n08: bdv c\n10: adv 3"
+        );
+    }
+
+    #[test]
+    fn test_instruction_padding() {
+        let padded =
+            |opcode, operand| format!("{:<6}|", Instruction::decode(opcode, operand).unwrap());
+        assert_eq!(padded(4, 0), "bxc   |");
+        assert_eq!(padded(0, 5), "adv b |");
+        assert_eq!(padded(3, 0), "jnz 0 |");
+        assert_eq!(
+            format!("{:>7}", Instruction::decode(5, 4).unwrap()),
+            "  out a"
+        );
+    }
+
+    #[test]
+    fn test_execute_trace() {
+        let (a, b, c, program) = parse(INPUT1);
+        let mut steps = Vec::new();
+        Vm::new(&program)
+            .run(Registers { a, b, c }, |ip, instruction, registers, _| {
+                steps.push((ip, instruction.opcode, registers.a));
+            })
+            .unwrap();
+        assert_eq!(steps.len(), 30);
+        assert_eq!(steps[0], (0, Opcode::Adv, 364));
+        assert_eq!(steps[1], (2, Opcode::Out, 364));
+        assert_eq!(steps[2], (4, Opcode::Jnz, 364));
+        assert_eq!(steps[29], (4, Opcode::Jnz, 0));
+    }
+
+    #[test]
+    fn test_vm_errors() {
+        let registers = Registers { a: 1, b: 0, c: 0 };
+        let run = |program: &[u64]| {
+            Vm::new(program)
+                .with_budget(100)
+                .run(registers, |_, _, _, _| {})
+        };
+        assert_eq!(run(&[0, 1, 5, 7]), Err(VmError::ReservedOperand { ip: 2 }));
+        assert_eq!(
+            run(&[5, 4, 9, 0]),
+            Err(VmError::InvalidOpcode { ip: 2, opcode: 9 })
+        );
+        assert_eq!(run(&[3, 0]), Err(VmError::BudgetExhausted { budget: 100 }));
+
+        let registers = Registers {
+            a: u64::MAX,
+            b: 64,
+            c: 200,
+        };
+        let output = Vm::new(&[7, 6, 0, 5, 6, 5, 5, 4, 5, 5, 5, 6]).run(registers, |_, _, _, _| {});
+        assert_eq!(output, Ok(vec![0, 0, 0]));
+    }
+
+    #[test]
+    fn test_extension_opcodes() {
+        let program = [8, 3, 5, 4, 9, 6, 5, 4];
+        let extensions: Extensions = "8=mul, 9=jmp".parse().unwrap();
+        let vm = Vm::new(&program).with_extensions(&extensions).unwrap();
+        assert_eq!(
+            vm.disassemble(),
+            "00: mul 3\n02: out a\n04: jmp 6\n06: out a"
+        );
+        let registers = Registers { a: 5, b: 0, c: 0 };
+        assert_eq!(vm.run(registers, |_, _, _, _| {}), Ok(vec![7, 7]));
+        assert_eq!(
+            Vm::new(&program).disassemble(),
+            "00: ??? 3\n02: out a\n04: ??? 6\n06: out a"
+        );
+
+        let swapped = Vm::new(&[8, 0, 5, 4])
+            .with_opcode(8, "swp", EXTENSIONS[2].1)
+            .unwrap();
+        let registers = Registers { a: 1, b: 2, c: 0 };
+        assert_eq!(swapped.run(registers, |_, _, _, _| {}), Ok(vec![2]));
+
+        let shadowing: Extensions = "8=add, 3=mul".parse().unwrap();
+        assert_eq!(
+            Vm::new(&program).with_extensions(&shadowing).err(),
+            Some(VmError::ReservedOpcode { code: 3 })
+        );
+        assert_eq!(
+            "8=div".parse::<Extensions>(),
+            Err("Unknown extension div".to_string())
+        );
+    }
+
     #[test]
     fn test_part1() {
         let (a, b, c, program) = parse(INPUT1);
@@ -127,4 +649,45 @@ mod tests {
         let quine_state = part2(&program);
         assert_eq!(quine_state, "117440");
     }
+
+    #[test]
+    fn test_find_quine() {
+        let program = [2, 4, 1, 1, 7, 5, 4, 6, 0, 3, 1, 4, 5, 5, 3, 0];
+        let a = find_quine(&program).unwrap();
+        assert_eq!(compute(a, 0, 0, &program), Ok(program.to_vec()));
+
+        assert_eq!(
+            find_quine(&[0, 2, 5, 4, 3, 0]),
+            Err("No value of A makes the program output itself".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unsupported_shapes() {
+        let shape = |program: &[u64]| analyse(program).unwrap_err();
+        assert_eq!(
+            shape(&[0, 3, 5, 4]),
+            "Program should be a single loop ending in jnz 0"
+        );
+        assert_eq!(
+            shape(&[0, 3, 5, 4, 5, 4, 3, 0]),
+            "Loop should output exactly one value per iteration"
+        );
+        assert_eq!(
+            shape(&[0, 4, 5, 4, 3, 0]),
+            "A should be shifted by a constant each iteration"
+        );
+        assert_eq!(
+            shape(&[1, 3, 5, 5, 3, 0]),
+            "Loop should shift A exactly once per iteration"
+        );
+        assert_eq!(
+            shape(&[0, 3, 5, 5, 3, 0]),
+            "Loop should not depend on B or C from the previous iteration"
+        );
+        assert_eq!(
+            analyse(&[2, 4, 0, 3, 5, 5, 3, 0]),
+            Ok(LoopShape { shift: 3 })
+        );
+    }
 }
@@ -1,7 +1,7 @@
 use super::DayResult;
 use crate::utils::{
     bench::time_execution,
-    grid::{Coord, Grid},
+    grid::{BitGrid, Coord, Grid},
 };
 use std::{collections::VecDeque, fs};
 
@@ -38,7 +38,7 @@ fn parse(input: &str) -> Vec<Coord> {
 
 fn bfs(memory_space: &Grid<u8>) -> Option<usize> {
     let (height, width) = (memory_space.height(), memory_space.width());
-    let mut seen = Grid::new(height, width, false);
+    let mut seen = BitGrid::new(height, width);
     let mut queue = VecDeque::from([(Coord::new(0, 0), 0)]);
     let end = Coord::new(height as isize - 1, width as isize - 1);
 
@@ -50,9 +50,8 @@ fn bfs(memory_space: &Grid<u8>) -> Option<usize> {
         for neighbour in coord.orthogonal_neighbours() {
             if memory_space.contains(neighbour)
                 && memory_space[neighbour] != b'#'
-                && !seen[neighbour]
+                && seen.insert(neighbour)
             {
-                seen[neighbour] = true;
                 queue.push_back((neighbour, dist + 1));
             }
         }
@@ -97,7 +96,7 @@ fn part2(height: usize, width: usize, corrupted: &[Coord]) -> String {
 mod tests {
     use super::*;
 
-    const INPUT: &'static str = "5,4\n4,2\n4,5\n3,0\n2,1\n6,3\n2,4\n1,5\n0,6\n3,3\n2,6\n5,1\n1,2\n5,5\n2,5\n6,5\n1,4\n0,4\n6,4\n1,1\n6,1\n1,0\n0,5\n1,6\n2,0";
+    const INPUT: &str = "5,4\n4,2\n4,5\n3,0\n2,1\n6,3\n2,4\n1,5\n0,6\n3,3\n2,6\n5,1\n1,2\n5,5\n2,5\n6,5\n1,4\n0,4\n6,4\n1,1\n6,1\n1,0\n0,5\n1,6\n2,0";
 
     #[test]
     fn test_parse() {
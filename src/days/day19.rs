@@ -1,5 +1,8 @@
 use super::DayResult;
-use crate::utils::bench::time_execution;
+use crate::utils::{
+    bench::time_execution,
+    parse::{normalise, Span},
+};
 use std::fs;
 
 pub fn run() -> DayResult {
@@ -64,10 +67,15 @@ impl<'a> FromIterator<&'a str> for TrieNode {
 }
 
 fn parse(input: &str) -> (TrieNode, Vec<String>) {
-    let input = input.replace('\r', "");
-    let (towels, designs) = input.split_once("\n\n").unwrap();
-    let towels = towels.split(", ").collect();
-    let designs = designs.lines().map(String::from).collect();
+    let input = normalise(input);
+    let [towels, designs] = Span::new(&input)
+        .sections_array()
+        .expect("Towels and designs should be separated by a blank line");
+    let towels = towels.split(", ").map(|towel| towel.as_str()).collect();
+    let designs = designs
+        .lines()
+        .map(|design| design.as_str().to_owned())
+        .collect();
     (towels, designs)
 }
 
@@ -105,7 +113,7 @@ fn part2(towels: &TrieNode, designs: &[String]) -> String {
 mod tests {
     use super::*;
 
-    const INPUT: &'static str =
+    const INPUT: &str =
         "r, wr, b, g, bwu, rb, gb, br\n\nbrwrr\nbggr\ngbbr\nrrbgbr\nubwu\nbwurrg\nbrgr\nbbrgwb";
 
     #[test]
@@ -1,5 +1,8 @@
 use super::DayResult;
-use crate::utils::{bench::time_execution, grid::Grid};
+use crate::utils::{
+    bench::time_execution,
+    grid::{BitGrid, Grid},
+};
 use rayon::prelude::*;
 use std::fs;
 
@@ -28,12 +31,12 @@ fn solve(racetrack: &Grid<u8>, cheat_time: usize, threshold: usize) -> usize {
         .position(b'S')
         .expect("Racetrack should have a start");
     let mut path = Vec::new();
-    let mut seen = Grid::new(height, width, false);
+    let mut seen = BitGrid::new(height, width);
     let mut program = start;
 
     loop {
         path.push(program);
-        seen[program] = true;
+        seen.insert(program);
         program = match program
             .orthogonal_neighbours()
             .into_iter()
@@ -75,7 +78,7 @@ mod tests {
     use super::*;
     use std::str;
 
-    const INPUT: &'static str = "###############\n#...#...#.....#\n#.#.#.#.#.###.#\n#S#...#.#.#...#\n#######.#.#.###\n#######.#.#...#\n#######.#.###.#\n###..E#...#...#\n###.#######.###\n#...###...#...#\n#.#####.#.###.#\n#.#...#.#.#...#\n#.#.#.#.#.#.###\n#...#...#...###\n###############";
+    const INPUT: &str = "###############\n#...#...#.....#\n#.#.#.#.#.###.#\n#S#...#.#.#...#\n#######.#.#.###\n#######.#.#...#\n#######.#.###.#\n###..E#...#...#\n###.#######.###\n#...###...#...#\n#.#####.#.###.#\n#.#...#.#.#...#\n#.#.#.#.#.#.###\n#...#...#...###\n###############";
 
     #[test]
     fn test_parse() {
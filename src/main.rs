@@ -1,13 +1,52 @@
 mod days;
 mod utils;
 
-use crate::utils::io::{input, output};
+use crate::utils::io::{
+    input::{self, Command, Options},
+    output,
+    replay::{self, Playback},
+};
 #[allow(clippy::wildcard_imports)]
 use days::*;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let day = input::parse_args()?;
+    match input::parse_args()? {
+        Command::Solve { day, options } => solve(day, &options),
+        Command::Replay { path, options } => {
+            options.reject_unknown(&["speed", "from", "to"])?;
+            let defaults = Playback::default();
+            let playback = Playback::new(
+                options.parsed("speed")?.unwrap_or(defaults.speed),
+                options.parsed("from")?.unwrap_or(defaults.from),
+                options.parsed("to")?,
+            )?;
+            replay::replay(path, playback)?;
+            Ok(())
+        }
+    }
+}
+
+const SOLVE_OPTIONS: [&str; 16] = [
+    "frames",
+    "from",
+    "count",
+    "record",
+    "trace",
+    "policy",
+    "budget",
+    "extensions",
+    "explain",
+    "tolerance",
+    "min-step",
+    "max-step",
+    "operators",
+    "report",
+    "max-digits",
+    "rule",
+];
 
+fn solve(day: u8, options: &Options) -> Result<(), Box<dyn std::error::Error>> {
+    options.reject_unknown(&SOLVE_OPTIONS)?;
     let day_result = match day {
         1 => Ok(day01::run()),
         2 => Ok(day02::run()),
@@ -34,5 +73,51 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     output::print_table(day_result, 15);
 
+    if let Some(dir) = options.required("frames")? {
+        let (from, count) = (options.parsed("from")?, options.parsed("count")?);
+        match day {
+            14 => day14::export_frames(dir, from, count)?,
+            15 => day15::export_frames(dir, from, count)?,
+            _ => return Err(format!("Day {day} cannot export frames").into()),
+        }
+    }
+
+    if let Some(path) = options.required("record")? {
+        match day {
+            6 => day06::record(path)?,
+            15 => day15::record(path)?,
+            _ => return Err(format!("Day {day} cannot be recorded").into()),
+        }
+    }
+
+    if options.flag("trace") {
+        match day {
+            9 => day09::trace(options.parsed("policy")?)?,
+            17 => day17::trace(options.parsed("budget")?, options.parsed("extensions")?)?,
+            _ => return Err(format!("Day {day} cannot be traced").into()),
+        }
+    }
+
+    if options.flag("explain") {
+        match day {
+            2 => day02::explain(
+                options.parsed("tolerance")?,
+                options.parsed("min-step")?,
+                options.parsed("max-step")?,
+            )?,
+            7 => day07::explain(options.parsed("operators")?)?,
+            _ => return Err(format!("Day {day} cannot be explained").into()),
+        }
+    }
+
+    if options.flag("report") {
+        match day {
+            3 => day03::report(options.parsed("max-digits")?)?,
+            6 => day06::report()?,
+            8 => day08::report(options.parsed("rule")?)?,
+            _ => return Err(format!("Day {day} has no report").into()),
+        }
+    }
+
     Ok(())
 }
@@ -0,0 +1,178 @@
+use std::{collections::HashMap, hash::Hash};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    pub fn index_of(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    pub fn fast_forward<S, F>(&self, initial: &S, mut step: F, n: usize) -> Option<S>
+    where
+        S: Clone,
+        F: FnMut(&S) -> Option<S>,
+    {
+        (0..self.index_of(n)).try_fold(initial.clone(), |state, _| step(&state))
+    }
+}
+
+pub fn floyd<S, F>(initial: &S, mut step: F) -> Option<Cycle>
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> Option<S>,
+{
+    let mut tortoise = step(initial)?;
+    let mut hare = step(&tortoise)?;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        hare = step(&hare)?;
+    }
+
+    let mut start = 0;
+    tortoise = initial.clone();
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise)?;
+    while tortoise != hare {
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+pub fn brent<S, F>(initial: &S, mut step: F) -> Option<Cycle>
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> Option<S>,
+{
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial)?;
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    tortoise = initial.clone();
+    hare = (0..length).try_fold(initial.clone(), |state, _| step(&state))?;
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+pub fn hashed<S, F>(initial: &S, mut step: F) -> Option<Cycle>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> Option<S>,
+{
+    let mut seen = HashMap::new();
+    let mut state = initial.clone();
+    for i in 0.. {
+        if let Some(&start) = seen.get(&state) {
+            return Some(Cycle {
+                start,
+                length: i - start,
+            });
+        }
+        let next = step(&state)?;
+        seen.insert(state, i);
+        state = next;
+    }
+    unreachable!("State space should not be exhausted")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rho(tail: usize, length: usize) -> impl Fn(&usize) -> Option<usize> {
+        move |&state| {
+            Some(if state + 1 == tail + length {
+                tail
+            } else {
+                state + 1
+            })
+        }
+    }
+
+    #[test]
+    fn test_known_cycles() {
+        for (tail, length) in [(0, 1), (0, 7), (1, 1), (3, 4), (10, 3), (5, 64)] {
+            let expected = Some(Cycle {
+                start: tail,
+                length,
+            });
+            assert_eq!(floyd(&0, rho(tail, length)), expected);
+            assert_eq!(brent(&0, rho(tail, length)), expected);
+            assert_eq!(hashed(&0, rho(tail, length)), expected);
+        }
+    }
+
+    #[test]
+    fn test_terminating_sequence() {
+        let step = |&state: &u32| (state < 20).then_some(state + 1);
+        assert_eq!(floyd(&0, step), None);
+        assert_eq!(brent(&0, step), None);
+        assert_eq!(hashed(&0, step), None);
+    }
+
+    #[test]
+    fn test_index_of() {
+        let cycle = Cycle {
+            start: 3,
+            length: 4,
+        };
+        let naive = |n| (0..n).fold(0, |state, _| rho(3, 4)(&state).unwrap());
+        for n in 0..20 {
+            assert_eq!(cycle.index_of(n), naive(n));
+        }
+        assert_eq!(cycle.index_of(1_000_000_000), 3 + (1_000_000_000 - 3) % 4);
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let cycle = brent(&0, rho(3, 4)).unwrap();
+        let mut calls = 0;
+        let mut step = |state: &usize| {
+            calls += 1;
+            rho(3, 4)(state)
+        };
+        assert_eq!(cycle.fast_forward(&0, &mut step, 2), Some(2));
+        assert_eq!(cycle.fast_forward(&0, &mut step, 3), Some(3));
+        assert_eq!(cycle.fast_forward(&0, &mut step, 9), Some(5));
+        assert_eq!(cycle.fast_forward(&0, &mut step, 1_000_000_002), Some(6));
+        assert_eq!(calls, 2 + 3 + 5 + 6);
+
+        let naive = |n| (0..n).fold(0, |state, _| rho(3, 4)(&state).unwrap());
+        for n in 0..20 {
+            assert_eq!(cycle.fast_forward(&0, rho(3, 4), n), Some(naive(n)));
+        }
+        assert_eq!(cycle.fast_forward(&0, |_| None::<usize>, 5), None);
+        assert_eq!(cycle.fast_forward(&0, |_| None::<usize>, 0), Some(0));
+    }
+}
@@ -0,0 +1,278 @@
+use super::{Coord, Coord2, Grid, Scalar};
+use std::ops::Index;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    height: usize,
+    width: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            height,
+            width,
+            words: vec![0; (height * width).div_ceil(WORD_BITS)],
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn area(&self) -> usize {
+        self.width * self.height
+    }
+
+    pub fn contains<S: Scalar>(&self, coord: Coord2<S>) -> bool {
+        coord
+            .to_usize()
+            .is_some_and(|(r, c)| r < self.height && c < self.width)
+    }
+
+    pub fn get<S: Scalar>(&self, coord: Coord2<S>) -> Option<bool> {
+        if !self.contains(coord) {
+            return None;
+        }
+        Some(self.is_set(coord))
+    }
+
+    pub fn is_set<S: Scalar>(&self, coord: Coord2<S>) -> bool {
+        let (word, bit) = self.locate(coord);
+        self.words[word] & bit != 0
+    }
+
+    pub fn set<S: Scalar>(&mut self, coord: Coord2<S>, value: bool) {
+        let (word, bit) = self.locate(coord);
+        if value {
+            self.words[word] |= bit;
+        } else {
+            self.words[word] &= !bit;
+        }
+    }
+
+    pub fn insert<S: Scalar>(&mut self, coord: Coord2<S>) -> bool {
+        let (word, bit) = self.locate(coord);
+        let was_set = self.words[word] & bit != 0;
+        self.words[word] |= bit;
+        !was_set
+    }
+
+    pub fn remove<S: Scalar>(&mut self, coord: Coord2<S>) -> bool {
+        let (word, bit) = self.locate(coord);
+        let was_set = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        was_set
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.assert_same_dimensions(other);
+        for (word, &other_word) in self.words.iter_mut().zip(&other.words) {
+            *word |= other_word;
+        }
+    }
+
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.assert_same_dimensions(other);
+        for (word, &other_word) in self.words.iter_mut().zip(&other.words) {
+            *word &= other_word;
+        }
+    }
+
+    pub fn union(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    pub fn intersection(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+
+    pub fn iter_ones(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let mut remaining = word;
+            std::iter::from_fn(move || {
+                if remaining == 0 {
+                    return None;
+                }
+                let bit = remaining.trailing_zeros() as usize;
+                remaining &= remaining - 1;
+                Some(self.index_to_coord(i * WORD_BITS + bit))
+            })
+        })
+    }
+
+    fn locate<S: Scalar>(&self, coord: Coord2<S>) -> (usize, u64) {
+        let (r, c) = coord
+            .to_usize()
+            .filter(|&(r, c)| r < self.height && c < self.width)
+            .unwrap_or_else(|| panic!("Coordinate out of bounds: {coord:?}"));
+        let index = r * self.width + c;
+        (index / WORD_BITS, 1 << (index % WORD_BITS))
+    }
+
+    fn index_to_coord(&self, index: usize) -> Coord {
+        Coord::from_usize(index / self.width, index % self.width)
+            .expect("Grid index should fit in a coordinate")
+    }
+
+    fn assert_same_dimensions(&self, other: &BitGrid) {
+        assert_eq!(
+            (self.height, self.width),
+            (other.height, other.width),
+            "Bit grids have different dimensions"
+        );
+    }
+}
+
+impl<S: Scalar> Index<Coord2<S>> for BitGrid {
+    type Output = bool;
+
+    fn index(&self, index: Coord2<S>) -> &Self::Output {
+        if self.is_set(index) {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(value: &Grid<bool>) -> Self {
+        let mut bits = BitGrid::new(value.height(), value.width());
+        for (coord, _) in value.enumerate_cells().filter(|(_, &cell)| cell) {
+            bits.insert(coord);
+        }
+        bits
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(value: &BitGrid) -> Self {
+        let mut grid = Grid::new(value.height, value.width, false);
+        for coord in value.iter_ones() {
+            grid[coord] = true;
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iter_ones_across_word_boundaries() {
+        let mut bits = BitGrid::new(3, 50);
+        let coords =
+            [(0, 0), (1, 13), (1, 14), (2, 27), (2, 28), (2, 49)].map(|(r, c)| Coord::new(r, c));
+        for coord in coords {
+            assert!(bits.insert(coord));
+        }
+        assert!(!bits.insert(coords[1]));
+        assert_eq!(bits.iter_ones().collect::<Vec<_>>(), coords);
+        assert_eq!(bits.count_ones(), coords.len());
+
+        assert!(bits.remove(Coord::new(1, 14)));
+        assert!(!bits.remove(Coord::new(1, 14)));
+        assert_eq!(bits.count_ones(), coords.len() - 1);
+
+        bits.clear();
+        assert!(bits.is_empty());
+        assert_eq!(bits.count_ones(), 0);
+        assert_eq!(bits.iter_ones().next(), None);
+    }
+
+    #[test]
+    fn test_union_and_intersection() {
+        let mut left = BitGrid::new(2, 40);
+        let mut right = BitGrid::new(2, 40);
+        left.set(Coord::new(0, 1), true);
+        left.set(Coord::new(1, 30), true);
+        right.set(Coord::new(1, 30), true);
+        right.set(Coord::new(1, 39), true);
+
+        let union = left.union(&right);
+        assert_eq!(union.count_ones(), 3);
+        assert_eq!(
+            left.intersection(&right).iter_ones().collect::<Vec<_>>(),
+            vec![Coord::new(1, 30)]
+        );
+        left.union_with(&right);
+        assert_eq!(left, union);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bit grids have different dimensions")]
+    fn test_union_with_mismatched_dimensions() {
+        let _ = BitGrid::new(2, 3).union(&BitGrid::new(3, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Bit grids have different dimensions")]
+    fn test_intersection_with_mismatched_dimensions() {
+        BitGrid::new(2, 3).intersect_with(&BitGrid::new(2, 4));
+    }
+
+    #[test]
+    fn test_grid_round_trip() {
+        let grid = Grid::from("#..#\n.##.\n#...").map(|&cell| cell == b'#');
+        let bits = BitGrid::from(&grid);
+        assert_eq!((bits.height(), bits.width(), bits.area()), (3, 4, 12));
+        assert_eq!(bits.count_ones(), 5);
+        assert!(bits[Coord::new(1, 2)]);
+        assert!(!bits[Coord::new(2, 3)]);
+        let round_trip = Grid::<bool>::from(&bits);
+        assert_eq!(
+            (round_trip.height(), round_trip.width()),
+            (grid.height(), grid.width())
+        );
+        assert!(round_trip.into_iter().eq(grid));
+    }
+
+    #[test]
+    fn test_out_of_bounds_get() {
+        let bits = BitGrid::new(2, 2);
+        assert_eq!(bits.get(Coord::new(1, 1)), Some(false));
+        assert_eq!(bits.get(Coord::new(2, 0)), None);
+        assert_eq!(bits.get(Coord::new(0, -1)), None);
+        assert!(!bits.contains(Coord::new(-1, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Coordinate out of bounds")]
+    fn test_out_of_bounds_set() {
+        BitGrid::new(2, 2).set(Coord::new(0, 2), true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Coordinate out of bounds")]
+    fn test_out_of_bounds_index() {
+        let _ = BitGrid::new(2, 2)[Coord::new(-1, 0)];
+    }
+}
@@ -1,22 +1,32 @@
-use super::Direction;
-use std::ops::{Add, AddAssign, Div, Mul, Rem, Sub, SubAssign};
+use super::{Direction, Scalar};
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Rem, Sub, SubAssign};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
-pub struct Coord {
-    pub r: isize,
-    pub c: isize,
+pub struct Coord2<T: Scalar> {
+    pub r: T,
+    pub c: T,
 }
 
-impl Coord {
-    pub fn new(r: isize, c: isize) -> Coord {
+pub type Coord = Coord2<isize>;
+
+impl<T: Scalar> Coord2<T> {
+    pub fn new(r: T, c: T) -> Self {
         Self { r, c }
     }
 
-    pub fn x(&self) -> isize {
+    pub fn from_usize(r: usize, c: usize) -> Option<Self> {
+        Some(Self::new(T::from_usize(r)?, T::from_usize(c)?))
+    }
+
+    pub fn to_usize(self) -> Option<(usize, usize)> {
+        Some((self.r.to_usize()?, self.c.to_usize()?))
+    }
+
+    pub fn x(&self) -> T {
         self.c
     }
 
-    pub fn y(&self) -> isize {
+    pub fn y(&self) -> T {
         self.r
     }
 
@@ -24,118 +34,310 @@ impl Coord {
         self.r.abs_diff(rhs.r) + self.c.abs_diff(rhs.c)
     }
 
-    pub fn orthogonal_neighbours(self) -> [Coord; 4] {
+    pub fn chebyshev_distance(&self, rhs: Self) -> usize {
+        self.r.abs_diff(rhs.r).max(self.c.abs_diff(rhs.c))
+    }
+
+    pub fn squared_euclidean_distance(&self, rhs: Self) -> usize {
+        let (dr, dc) = (self.r.abs_diff(rhs.r), self.c.abs_diff(rhs.c));
+        dr * dr + dc * dc
+    }
+
+    pub fn reduced(self) -> Self {
+        let gcd = self.r.gcd(self.c);
+        if gcd == T::ZERO {
+            self
+        } else {
+            self / gcd
+        }
+    }
+
+    pub fn direction_to(self, rhs: Self) -> Self {
+        (rhs - self).reduced()
+    }
+
+    pub fn is_collinear(self, a: Self, b: Self) -> bool {
+        let (u, v) = (a - self, b - self);
+        u.r * v.c == u.c * v.r
+    }
+
+    pub fn ray(self, step: Self) -> impl Iterator<Item = Self> {
+        std::iter::successors(Some(self), move |&coord| Some(coord + step))
+    }
+
+    pub fn line_to(self, end: Self) -> Line<T> {
+        Line::new(self, end)
+    }
+
+    pub fn orthogonal_neighbours(self) -> [Self; 4] {
         Direction::ORTHOGONAL.map(|dir| self + dir)
     }
 
-    pub fn diagonal_neighbours(self) -> [Coord; 4] {
+    pub fn diagonal_neighbours(self) -> [Self; 4] {
         Direction::DIAGONAL.map(|dir| self + dir)
     }
 
-    pub fn all_neighbours(self) -> [Coord; 8] {
+    pub fn all_neighbours(self) -> [Self; 8] {
         Direction::ALL.map(|dir| self + dir)
     }
 }
 
-impl Add for Coord {
-    type Output = Coord;
+#[derive(Debug, Clone)]
+pub struct Line<T: Scalar> {
+    current: Option<Coord2<T>>,
+    end: Coord2<T>,
+    step: Coord2<T>,
+    delta: Coord2<T>,
+    error: T,
+}
+
+impl<T: Scalar> Line<T> {
+    fn new(start: Coord2<T>, end: Coord2<T>) -> Self {
+        let diff = end - start;
+        let delta = Coord2::new(diff.r.abs(), -diff.c.abs());
+        Self {
+            current: Some(start),
+            end,
+            step: Coord2::new(diff.r.signum(), diff.c.signum()),
+            delta,
+            error: delta.r + delta.c,
+        }
+    }
+}
+
+impl<T: Scalar> Iterator for Line<T> {
+    type Item = Coord2<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current?;
+        if current == self.end {
+            self.current = None;
+            return Some(current);
+        }
+
+        let mut next = current;
+        let doubled = self.error + self.error;
+        if doubled >= self.delta.c {
+            self.error += self.delta.c;
+            next.r += self.step.r;
+        }
+        if doubled <= self.delta.r {
+            self.error += self.delta.r;
+            next.c += self.step.c;
+        }
+        self.current = Some(next);
+        Some(current)
+    }
+}
+
+impl<T: Scalar> Add for Coord2<T> {
+    type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Coord::new(self.r + rhs.r, self.c + rhs.c)
+        Self::new(self.r + rhs.r, self.c + rhs.c)
     }
 }
 
-impl Add<Direction> for Coord {
-    type Output = Coord;
+impl<T: Scalar> Add<Direction> for Coord2<T> {
+    type Output = Self;
 
     fn add(self, rhs: Direction) -> Self::Output {
-        self + Coord::from(rhs)
+        self + Self::from(rhs)
     }
 }
 
-impl AddAssign for Coord {
+impl<T: Scalar> AddAssign for Coord2<T> {
     fn add_assign(&mut self, rhs: Self) {
         self.r += rhs.r;
         self.c += rhs.c;
     }
 }
 
-impl AddAssign<Direction> for Coord {
+impl<T: Scalar> AddAssign<Direction> for Coord2<T> {
     fn add_assign(&mut self, rhs: Direction) {
-        *self += Coord::from(rhs);
+        *self += Self::from(rhs);
     }
 }
 
-impl Sub for Coord {
-    type Output = Coord;
+impl<T: Scalar> Sub for Coord2<T> {
+    type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        Coord::new(self.r - rhs.r, self.c - rhs.c)
+        Self::new(self.r - rhs.r, self.c - rhs.c)
     }
 }
 
-impl SubAssign for Coord {
+impl<T: Scalar> SubAssign for Coord2<T> {
     fn sub_assign(&mut self, rhs: Self) {
         self.r -= rhs.r;
         self.c -= rhs.c;
     }
 }
 
-impl Mul<isize> for Coord {
-    type Output = Coord;
+impl<T: Scalar> Neg for Coord2<T> {
+    type Output = Self;
 
-    fn mul(self, rhs: isize) -> Self::Output {
-        Coord::new(self.r * rhs, self.c * rhs)
+    fn neg(self) -> Self::Output {
+        Self::new(-self.r, -self.c)
     }
 }
 
-impl Mul<Coord> for isize {
-    type Output = Coord;
+impl<T: Scalar> Mul<T> for Coord2<T> {
+    type Output = Self;
 
-    fn mul(self, rhs: Coord) -> Self::Output {
-        Coord::new(self * rhs.r, self * rhs.c)
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.r * rhs, self.c * rhs)
     }
 }
 
-impl Div<isize> for Coord {
-    type Output = Coord;
+impl<T: Scalar> Div<T> for Coord2<T> {
+    type Output = Self;
 
-    fn div(self, rhs: isize) -> Self::Output {
-        Coord::new(self.r / rhs, self.c / rhs)
+    fn div(self, rhs: T) -> Self::Output {
+        Self::new(self.r / rhs, self.c / rhs)
     }
 }
 
-impl Rem<isize> for Coord {
-    type Output = Coord;
+impl<T: Scalar> Rem<T> for Coord2<T> {
+    type Output = Self;
 
-    fn rem(self, rhs: isize) -> Self::Output {
-        Coord::new(self.r % rhs, self.c % rhs)
+    fn rem(self, rhs: T) -> Self::Output {
+        Self::new(self.r % rhs, self.c % rhs)
     }
 }
 
-impl From<(isize, isize)> for Coord {
-    fn from(value: (isize, isize)) -> Self {
-        Coord::new(value.0, value.1)
+macro_rules! impl_scalar_mul {
+    ($($t:ty),*) => {
+        $(
+            impl Mul<Coord2<$t>> for $t {
+                type Output = Coord2<$t>;
+
+                fn mul(self, rhs: Coord2<$t>) -> Self::Output {
+                    Coord2::new(self * rhs.r, self * rhs.c)
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_mul!(i32, i64, isize);
+
+impl<T: Scalar> From<(T, T)> for Coord2<T> {
+    fn from(value: (T, T)) -> Self {
+        Self::new(value.0, value.1)
     }
 }
 
-impl From<Coord> for (isize, isize) {
-    fn from(value: Coord) -> Self {
+impl<T: Scalar> From<Coord2<T>> for (T, T) {
+    fn from(value: Coord2<T>) -> Self {
         (value.r, value.c)
     }
 }
 
-impl From<Direction> for Coord {
+impl<T: Scalar> From<Direction> for Coord2<T> {
     fn from(value: Direction) -> Self {
+        let (zero, one) = (T::ZERO, T::ONE);
         match value {
-            Direction::North => Coord::new(-1, 0),
-            Direction::NorthEast => Coord::new(-1, 1),
-            Direction::East => Coord::new(0, 1),
-            Direction::SouthEast => Coord::new(1, 1),
-            Direction::South => Coord::new(1, 0),
-            Direction::SouthWest => Coord::new(1, -1),
-            Direction::West => Coord::new(0, -1),
-            Direction::NorthWest => Coord::new(-1, -1),
+            Direction::North => Self::new(-one, zero),
+            Direction::NorthEast => Self::new(-one, one),
+            Direction::East => Self::new(zero, one),
+            Direction::SouthEast => Self::new(one, one),
+            Direction::South => Self::new(one, zero),
+            Direction::SouthWest => Self::new(one, -one),
+            Direction::West => Self::new(zero, -one),
+            Direction::NorthWest => Self::new(-one, -one),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Coord = Coord2<isize>;
+
+    #[test]
+    fn test_line_octants() {
+        let origin = Coord::new(0, 0);
+        for (dr, dc) in [
+            (2, 5),
+            (5, 2),
+            (-2, 5),
+            (-5, 2),
+            (2, -5),
+            (5, -2),
+            (-2, -5),
+            (-5, -2),
+        ] {
+            let end = Coord::new(dr, dc);
+            let line: Vec<_> = origin.line_to(end).collect();
+            assert_eq!(line.len(), 6, "{end:?}");
+            assert_eq!((line[0], line[5]), (origin, end));
+            for (a, b) in line.iter().zip(&line[1..]) {
+                assert_eq!(a.chebyshev_distance(*b), 1, "{end:?}");
+            }
+            for point in &line {
+                assert!(2 * (point.r * dc - point.c * dr).abs() <= dr.abs().max(dc.abs()));
+            }
+        }
+        assert_eq!(
+            origin.line_to(Coord::new(2, 5)).collect::<Vec<_>>(),
+            [(0, 0), (0, 1), (1, 2), (1, 3), (2, 4), (2, 5)].map(Coord::from)
+        );
+        assert_eq!(
+            origin.line_to(Coord::new(-5, 2)).collect::<Vec<_>>(),
+            [(0, 0), (-1, 0), (-2, 1), (-3, 1), (-4, 2), (-5, 2)].map(Coord::from)
+        );
+    }
+
+    #[test]
+    fn test_line_axes_and_diagonals() {
+        let start = Coord::new(3, -1);
+        assert_eq!(start.line_to(start).collect::<Vec<_>>(), [start]);
+        for dir in Direction::ALL {
+            let end = start + Coord::from(dir) * 4;
+            assert_eq!(
+                start.line_to(end).collect::<Vec<_>>(),
+                start.ray(dir.into()).take(5).collect::<Vec<_>>()
+            );
         }
     }
+
+    #[test]
+    fn test_ray() {
+        assert_eq!(
+            Coord::new(1, 1)
+                .ray(Coord::new(-2, 3))
+                .take(3)
+                .collect::<Vec<_>>(),
+            [(1, 1), (-1, 4), (-3, 7)].map(Coord::from)
+        );
+        assert!(Coord::new(4, 4)
+            .ray(Coord::new(0, 0))
+            .take(3)
+            .all(|c| c == Coord::new(4, 4)));
+    }
+
+    #[test]
+    fn test_reduced() {
+        assert_eq!(Coord::new(4, 6).reduced(), Coord::new(2, 3));
+        assert_eq!(Coord::new(-4, 6).reduced(), Coord::new(-2, 3));
+        assert_eq!(Coord::new(-6, -9).reduced(), Coord::new(-2, -3));
+        assert_eq!(Coord::new(0, -5).reduced(), Coord::new(0, -1));
+        assert_eq!(Coord::new(0, 0).reduced(), Coord::new(0, 0));
+        assert_eq!(
+            Coord::new(1, 7).direction_to(Coord::new(-5, 4)),
+            Coord::new(-2, -1)
+        );
+    }
+
+    #[test]
+    fn test_is_collinear() {
+        let origin = Coord::new(0, 0);
+        assert!(origin.is_collinear(Coord::new(2, 3), Coord::new(-4, -6)));
+        assert!(origin.is_collinear(Coord::new(0, 3), Coord::new(0, -1)));
+        assert!(origin.is_collinear(origin, Coord::new(5, 1)));
+        assert!(!origin.is_collinear(Coord::new(2, 3), Coord::new(3, 2)));
+        assert!(!Coord::new(1, 1).is_collinear(Coord::new(2, 2), Coord::new(3, 4)));
+    }
 }
@@ -1,3 +1,6 @@
+use super::{Coord2, Scalar};
+use std::{fmt, str::FromStr};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Direction {
     #[default]
@@ -42,7 +45,35 @@ impl Direction {
     }
 
     pub fn rotated(self, degrees: i16) -> Self {
-        Self::ALL[(self as i16 + (degrees / 45)).rem_euclid(8) as usize]
+        self.try_rotated(degrees)
+            .unwrap_or_else(|| panic!("Cannot rotate by {degrees} degrees, use multiples of 45"))
+    }
+
+    pub fn try_rotated(self, degrees: i16) -> Option<Self> {
+        if degrees % 45 != 0 {
+            return None;
+        }
+        Some(Self::ALL[(self as i16 + (degrees / 45)).rem_euclid(8) as usize])
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.rotated(-90)
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.rotated(90)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotated(180)
+    }
+
+    pub fn is_orthogonal(self) -> bool {
+        (self as u8).is_multiple_of(2)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !self.is_orthogonal()
     }
 
     pub fn is_opposite_to(self, other: Direction) -> bool {
@@ -51,6 +82,185 @@ impl Direction {
 
     pub fn is_orthogonal_to(self, other: Direction) -> bool {
         let (s, o) = (self as u8, other as u8);
-        (s + 2).rem_euclid(8) == o || (s - 2).rem_euclid(8) == o
+        (s + 2) % 8 == o || (s + 6) % 8 == o
+    }
+
+    pub fn all_clockwise(self) -> impl Iterator<Item = Direction> {
+        (0..8).map(move |i| Self::ALL[(self as usize + i) % 8])
+    }
+
+    pub fn orthogonal_clockwise(self) -> impl Iterator<Item = Direction> {
+        assert!(
+            self.is_orthogonal(),
+            "Orthogonal rotation should start from an orthogonal direction, got {self:?}"
+        );
+        let start = self as usize / 2;
+        (0..4).map(move |i| Self::ORTHOGONAL[(start + i) % 4])
+    }
+
+    pub fn ascii_glyph(self) -> u8 {
+        match self {
+            Direction::North => b'^',
+            Direction::East => b'>',
+            Direction::South => b'v',
+            Direction::West => b'<',
+            Direction::NorthEast | Direction::SouthWest => b'/',
+            Direction::SouthEast | Direction::NorthWest => b'\\',
+        }
+    }
+}
+
+impl From<Direction> for char {
+    fn from(value: Direction) -> Self {
+        match value {
+            Direction::North => '^',
+            Direction::NorthEast => '↗',
+            Direction::East => '>',
+            Direction::SouthEast => '↘',
+            Direction::South => 'v',
+            Direction::SouthWest => '↙',
+            Direction::West => '<',
+            Direction::NorthWest => '↖',
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", char::from(*self))
+    }
+}
+
+impl TryFrom<u8> for Direction {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            b'^' | b'U' | b'u' | b'N' | b'n' => Ok(Direction::North),
+            b'>' | b'R' | b'r' | b'E' | b'e' => Ok(Direction::East),
+            b'v' | b'D' | b'd' | b'S' | b's' => Ok(Direction::South),
+            b'<' | b'L' | b'l' | b'W' | b'w' => Ok(Direction::West),
+            _ => Err(format!("Invalid direction: {}", value.escape_ascii())),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let [byte] = s.as_bytes() {
+            return Direction::try_from(*byte);
+        }
+        let normalised: String = s
+            .chars()
+            .filter(|ch| !matches!(ch, '-' | '_' | ' '))
+            .map(|ch| ch.to_ascii_lowercase())
+            .collect();
+        match normalised.as_str() {
+            "north" | "up" => Ok(Direction::North),
+            "northeast" | "ne" => Ok(Direction::NorthEast),
+            "east" | "right" => Ok(Direction::East),
+            "southeast" | "se" => Ok(Direction::SouthEast),
+            "south" | "down" => Ok(Direction::South),
+            "southwest" | "sw" => Ok(Direction::SouthWest),
+            "west" | "left" => Ok(Direction::West),
+            "northwest" | "nw" => Ok(Direction::NorthWest),
+            "↗" => Ok(Direction::NorthEast),
+            "↘" => Ok(Direction::SouthEast),
+            "↙" => Ok(Direction::SouthWest),
+            "↖" => Ok(Direction::NorthWest),
+            _ => Err(format!("Invalid direction: {s}")),
+        }
+    }
+}
+
+impl<T: Scalar> TryFrom<Coord2<T>> for Direction {
+    type Error = String;
+
+    fn try_from(value: Coord2<T>) -> Result<Self, Self::Error> {
+        Direction::ALL
+            .into_iter()
+            .find(|&dir| Coord2::from(dir) == value)
+            .ok_or_else(|| format!("Not a unit step: {value:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Direction::*;
+
+    #[test]
+    fn test_try_from_u8() {
+        for (bytes, dir) in [
+            (b"^UuNn", North),
+            (b">RrEe", East),
+            (b"vDdSs", South),
+            (b"<LlWw", West),
+        ] {
+            for &byte in bytes {
+                assert_eq!(Direction::try_from(byte), Ok(dir));
+            }
+        }
+        assert_eq!(
+            Direction::try_from(b'\n'),
+            Err("Invalid direction: \\n".to_owned())
+        );
+        assert!(Direction::try_from(b'x').is_err());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.to_string().parse(), Ok(dir));
+            assert_eq!(format!("{dir:?}").parse(), Ok(dir));
+        }
+        assert_eq!("north-east".parse(), Ok(NorthEast));
+        assert_eq!("SW".parse(), Ok(SouthWest));
+        assert_eq!("Left".parse(), Ok(West));
+        assert!("up-ish".parse::<Direction>().is_err());
+        assert!("".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn test_rotation() {
+        assert_eq!(North.try_rotated(45), Some(NorthEast));
+        assert_eq!(North.try_rotated(-90), Some(West));
+        assert_eq!(West.try_rotated(450), Some(North));
+        assert_eq!(East.try_rotated(0), Some(East));
+        for degrees in [1, 30, -44, 100] {
+            assert_eq!(North.try_rotated(degrees), None);
+        }
+        assert_eq!(SouthEast.turn_left(), NorthEast);
+        assert_eq!(SouthEast.turn_right(), SouthWest);
+        assert_eq!(NorthWest.reverse(), SouthEast);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot rotate by 10 degrees")]
+    fn test_rotated_rejects_non_multiples() {
+        North.rotated(10);
+    }
+
+    #[test]
+    fn test_clockwise_order() {
+        assert_eq!(
+            West.all_clockwise().collect::<Vec<_>>(),
+            [West, NorthWest, North, NorthEast, East, SouthEast, South, SouthWest]
+        );
+        assert_eq!(
+            South.orthogonal_clockwise().collect::<Vec<_>>(),
+            [South, West, North, East]
+        );
+        assert!(North.is_opposite_to(South));
+        assert!(NorthEast.is_orthogonal_to(SouthEast));
+        assert!(!NorthEast.is_orthogonal_to(East));
+    }
+
+    #[test]
+    #[should_panic(expected = "Orthogonal rotation should start from an orthogonal direction")]
+    fn test_orthogonal_clockwise_rejects_diagonals() {
+        let _ = NorthEast.orthogonal_clockwise();
     }
 }
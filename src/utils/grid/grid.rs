@@ -1,4 +1,4 @@
-use super::Coord;
+use super::{Coord, Coord2, Scalar};
 use std::ops::{Index, IndexMut};
 
 #[derive(Debug, Clone)]
@@ -29,14 +29,19 @@ impl<T: Copy + PartialEq> Grid<T> {
         self.width * self.height
     }
 
-    pub fn get(&self, coord: Coord) -> Option<&T> {
+    pub fn dimensions(&self) -> Coord {
+        Coord::from_usize(self.height, self.width)
+            .expect("Grid dimensions should fit in a coordinate")
+    }
+
+    pub fn get<S: Scalar>(&self, coord: Coord2<S>) -> Option<&T> {
         if !self.contains(coord) {
             return None;
         }
         self.cells.get(self.coord_to_index(coord))
     }
 
-    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+    pub fn get_mut<S: Scalar>(&mut self, coord: Coord2<S>) -> Option<&mut T> {
         if !self.contains(coord) {
             return None;
         }
@@ -44,8 +49,18 @@ impl<T: Copy + PartialEq> Grid<T> {
         self.cells.get_mut(index)
     }
 
-    pub fn contains(&self, coord: Coord) -> bool {
-        (0..self.height as isize).contains(&coord.r) && (0..self.width as isize).contains(&coord.c)
+    pub fn contains<S: Scalar>(&self, coord: Coord2<S>) -> bool {
+        coord
+            .to_usize()
+            .is_some_and(|(r, c)| r < self.height && c < self.width)
+    }
+
+    pub fn wrap(&self, coord: Coord) -> Coord {
+        let dimensions = self.dimensions();
+        Coord::new(
+            coord.r.rem_euclid(dimensions.r),
+            coord.c.rem_euclid(dimensions.c),
+        )
     }
 
     pub fn row(&self, r: usize) -> &[T] {
@@ -53,12 +68,16 @@ impl<T: Copy + PartialEq> Grid<T> {
         &self.cells[r..r + self.width]
     }
 
-    fn coord_to_index(&self, coord: Coord) -> usize {
-        coord.r as usize * self.width + coord.c as usize
+    fn coord_to_index<S: Scalar>(&self, coord: Coord2<S>) -> usize {
+        let (r, c) = coord
+            .to_usize()
+            .unwrap_or_else(|| panic!("Coordinate should be non-negative: {coord:?}"));
+        r * self.width + c
     }
 
     fn index_to_coord(&self, index: usize) -> Coord {
-        Coord::new((index / self.width) as isize, (index % self.width) as isize)
+        Coord::from_usize(index / self.width, index % self.width)
+            .expect("Grid index should fit in a coordinate")
     }
 
     pub fn enumerate_cells(&self) -> impl Iterator<Item = (Coord, &T)> {
@@ -80,21 +99,46 @@ impl<T: Copy + PartialEq> Grid<T> {
             .map(|(coord, _)| coord)
     }
 
+    pub fn ray(&self, from: Coord, step: Coord) -> impl Iterator<Item = Coord> + '_ {
+        from.ray(step).take_while(|&coord| self.contains(coord))
+    }
+
+    pub fn has_line_of_sight<F>(&self, from: Coord, to: Coord, is_opaque: F) -> bool
+    where
+        F: Fn(&T) -> bool,
+    {
+        from.line_to(to)
+            .filter(|&coord| coord != from && coord != to)
+            .all(|coord| self.get(coord).is_some_and(|cell| !is_opaque(cell)))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        U: Copy + PartialEq,
+        F: Fn(&T) -> U,
+    {
+        Grid {
+            height: self.height,
+            width: self.width,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
     pub fn fill(&mut self, value: T) {
         self.cells.fill(value);
     }
 }
 
-impl<T: Copy + PartialEq> Index<Coord> for Grid<T> {
+impl<T: Copy + PartialEq, S: Scalar> Index<Coord2<S>> for Grid<T> {
     type Output = T;
 
-    fn index(&self, index: Coord) -> &Self::Output {
+    fn index(&self, index: Coord2<S>) -> &Self::Output {
         &self.cells[self.coord_to_index(index)]
     }
 }
 
-impl<T: Copy + PartialEq> IndexMut<Coord> for Grid<T> {
-    fn index_mut(&mut self, index: Coord) -> &mut Self::Output {
+impl<T: Copy + PartialEq, S: Scalar> IndexMut<Coord2<S>> for Grid<T> {
+    fn index_mut(&mut self, index: Coord2<S>) -> &mut Self::Output {
         let index = self.coord_to_index(index);
         &mut self.cells[index]
     }
@@ -131,14 +175,25 @@ impl From<&str> for Grid<u8> {
 
 impl std::fmt::Display for Grid<u8> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in 0..self.height {
-            for col in 0..self.width {
-                let index = self.coord_to_index(Coord::new(row as isize, col as isize));
-                let ch = self.cells[index] as char;
-                write!(f, "{ch}")?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        write!(f, "{}", self.render(|&cell| cell as char))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_line_of_sight() {
+        let grid = Grid::from("A.#.\n.#..\n...B");
+        let is_wall = |&cell: &u8| cell == b'#';
+        let (a, b) = (Coord::new(0, 0), Coord::new(2, 3));
+        assert!(grid.has_line_of_sight(a, Coord::new(0, 2), is_wall));
+        assert!(!grid.has_line_of_sight(a, Coord::new(0, 3), is_wall));
+        assert!(grid.has_line_of_sight(a, Coord::new(1, 1), is_wall));
+        assert!(!grid.has_line_of_sight(a, Coord::new(2, 2), is_wall));
+        assert!(grid.has_line_of_sight(b, Coord::new(0, 2), is_wall));
+        assert!(grid.has_line_of_sight(a, a, is_wall));
+        assert!(!grid.has_line_of_sight(b, Coord::new(2, 5), is_wall));
     }
 }
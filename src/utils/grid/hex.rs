@@ -0,0 +1,439 @@
+use super::{Coord, Direction, Grid};
+use std::{
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct HexCoord {
+    pub q: isize,
+    pub r: isize,
+}
+
+impl HexCoord {
+    pub fn new(q: isize, r: isize) -> Self {
+        Self { q, r }
+    }
+
+    pub fn from_cube(q: isize, r: isize, s: isize) -> Self {
+        assert_eq!(q + r + s, 0, "Cube coordinates should sum to zero");
+        Self { q, r }
+    }
+
+    pub fn s(&self) -> isize {
+        -self.q - self.r
+    }
+
+    pub fn cube(&self) -> (isize, isize, isize) {
+        (self.q, self.r, self.s())
+    }
+
+    pub fn distance(&self, rhs: Self) -> usize {
+        let diff = *self - rhs;
+        (diff.q.unsigned_abs() + diff.r.unsigned_abs() + diff.s().unsigned_abs()) / 2
+    }
+
+    pub fn neighbours(self) -> [HexCoord; 6] {
+        HexDirection::ALL.map(|dir| self + dir)
+    }
+
+    pub fn ring(self, radius: usize) -> impl Iterator<Item = HexCoord> {
+        let steps = isize::try_from(radius).expect("Ring radius should fit in isize");
+        let mut hex = self + HexCoord::from(HexDirection::SouthWest) * steps;
+        let ring = HexDirection::ALL
+            .into_iter()
+            .flat_map(move |dir| std::iter::repeat_n(dir, radius))
+            .map(move |dir| {
+                let current = hex;
+                hex += dir;
+                current
+            });
+        std::iter::once(self)
+            .filter(move |_| radius == 0)
+            .chain(ring)
+    }
+
+    pub fn spiral(self, radius: usize) -> impl Iterator<Item = HexCoord> {
+        (0..=radius).flat_map(move |ring| self.ring(ring))
+    }
+}
+
+impl Add for HexCoord {
+    type Output = HexCoord;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        HexCoord::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl Add<HexDirection> for HexCoord {
+    type Output = HexCoord;
+
+    fn add(self, rhs: HexDirection) -> Self::Output {
+        self + HexCoord::from(rhs)
+    }
+}
+
+impl AddAssign for HexCoord {
+    fn add_assign(&mut self, rhs: Self) {
+        self.q += rhs.q;
+        self.r += rhs.r;
+    }
+}
+
+impl AddAssign<HexDirection> for HexCoord {
+    fn add_assign(&mut self, rhs: HexDirection) {
+        *self += HexCoord::from(rhs);
+    }
+}
+
+impl Sub for HexCoord {
+    type Output = HexCoord;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        HexCoord::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl SubAssign for HexCoord {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.q -= rhs.q;
+        self.r -= rhs.r;
+    }
+}
+
+impl Neg for HexCoord {
+    type Output = HexCoord;
+
+    fn neg(self) -> Self::Output {
+        HexCoord::new(-self.q, -self.r)
+    }
+}
+
+impl Mul<isize> for HexCoord {
+    type Output = HexCoord;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        HexCoord::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HexDirection {
+    #[default]
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::NorthEast,
+        HexDirection::NorthWest,
+        HexDirection::West,
+        HexDirection::SouthWest,
+        HexDirection::SouthEast,
+    ];
+
+    pub fn rotated(self, sixths: i8) -> Self {
+        Self::ALL[(self as i8 + sixths).rem_euclid(6) as usize]
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotated(3)
+    }
+
+    pub fn parse_flat(s: &str) -> Result<Self, String> {
+        match s {
+            "n" => Ok(HexDirection::NorthWest),
+            "ne" => Ok(HexDirection::NorthEast),
+            "se" => Ok(HexDirection::East),
+            "s" => Ok(HexDirection::SouthEast),
+            "sw" => Ok(HexDirection::SouthWest),
+            "nw" => Ok(HexDirection::West),
+            _ => Err(format!("Invalid flat-top hex direction: {s}")),
+        }
+    }
+}
+
+impl FromStr for HexDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "e" => Ok(HexDirection::East),
+            "ne" => Ok(HexDirection::NorthEast),
+            "nw" => Ok(HexDirection::NorthWest),
+            "w" => Ok(HexDirection::West),
+            "sw" => Ok(HexDirection::SouthWest),
+            "se" => Ok(HexDirection::SouthEast),
+            _ => Err(format!("Invalid hex direction: {s}")),
+        }
+    }
+}
+
+impl From<HexDirection> for Direction {
+    fn from(value: HexDirection) -> Self {
+        match value {
+            HexDirection::East => Direction::East,
+            HexDirection::NorthEast => Direction::NorthEast,
+            HexDirection::NorthWest => Direction::North,
+            HexDirection::West => Direction::West,
+            HexDirection::SouthWest => Direction::SouthWest,
+            HexDirection::SouthEast => Direction::South,
+        }
+    }
+}
+
+impl From<HexDirection> for HexCoord {
+    fn from(value: HexDirection) -> Self {
+        match value {
+            HexDirection::East => HexCoord::new(1, 0),
+            HexDirection::NorthEast => HexCoord::new(1, -1),
+            HexDirection::NorthWest => HexCoord::new(0, -1),
+            HexDirection::West => HexCoord::new(-1, 0),
+            HexDirection::SouthWest => HexCoord::new(-1, 1),
+            HexDirection::SouthEast => HexCoord::new(0, 1),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HexGrid<T: Copy + PartialEq> {
+    radius: usize,
+    cells: Grid<T>,
+}
+
+impl<T: Copy + PartialEq> HexGrid<T> {
+    pub fn new(radius: usize, default_value: T) -> Self {
+        let side = 2 * radius + 1;
+        Self {
+            radius,
+            cells: Grid::new(side, side, default_value),
+        }
+    }
+
+    pub fn radius(&self) -> usize {
+        self.radius
+    }
+
+    pub fn area(&self) -> usize {
+        3 * self.radius * (self.radius + 1) + 1
+    }
+
+    pub fn contains(&self, coord: HexCoord) -> bool {
+        coord.distance(HexCoord::default()) <= self.radius
+    }
+
+    pub fn get(&self, coord: HexCoord) -> Option<&T> {
+        if !self.contains(coord) {
+            return None;
+        }
+        self.cells.get(self.to_coord(coord))
+    }
+
+    pub fn get_mut(&mut self, coord: HexCoord) -> Option<&mut T> {
+        if !self.contains(coord) {
+            return None;
+        }
+        let coord = self.to_coord(coord);
+        self.cells.get_mut(coord)
+    }
+
+    pub fn neighbours(&self, coord: HexCoord) -> impl Iterator<Item = HexCoord> + '_ {
+        coord
+            .neighbours()
+            .into_iter()
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+
+    pub fn enumerate_cells(&self) -> impl Iterator<Item = (HexCoord, &T)> {
+        HexCoord::default()
+            .spiral(self.radius)
+            .map(|coord| (coord, &self[coord]))
+    }
+
+    pub fn positions(&self, target: T) -> impl Iterator<Item = HexCoord> + use<'_, T> {
+        self.enumerate_cells()
+            .filter(move |(_, cell)| **cell == target)
+            .map(|(coord, _)| coord)
+    }
+
+    pub fn fill(&mut self, value: T) {
+        self.cells.fill(value);
+    }
+
+    pub fn as_grid(&self) -> &Grid<T> {
+        &self.cells
+    }
+
+    pub fn to_coord(&self, coord: HexCoord) -> Coord {
+        let centre = Coord::from_usize(self.radius, self.radius)
+            .expect("Hex grid radius should fit in a coordinate");
+        centre + Coord::new(coord.r, coord.q)
+    }
+
+    pub fn to_hex(&self, coord: Coord) -> HexCoord {
+        let centre = Coord::from_usize(self.radius, self.radius)
+            .expect("Hex grid radius should fit in a coordinate");
+        let offset = coord - centre;
+        HexCoord::new(offset.c, offset.r)
+    }
+
+    pub fn layout_coord(&self, coord: HexCoord) -> Coord {
+        let offset = self.to_coord(coord);
+        Coord::new(offset.r, 2 * offset.c + coord.r)
+    }
+
+    pub fn layout(&self) -> Grid<Option<T>> {
+        let side = 2 * self.radius + 1;
+        let mut layout = Grid::new(side, 2 * side - 1, None);
+        for (coord, &cell) in self.enumerate_cells() {
+            layout[self.layout_coord(coord)] = Some(cell);
+        }
+        layout
+    }
+}
+
+impl<T: Copy + PartialEq> Index<HexCoord> for HexGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: HexCoord) -> &Self::Output {
+        assert!(self.contains(index), "Hex coordinate out of bounds");
+        &self.cells[self.to_coord(index)]
+    }
+}
+
+impl<T: Copy + PartialEq> IndexMut<HexCoord> for HexGrid<T> {
+    fn index_mut(&mut self, index: HexCoord) -> &mut Self::Output {
+        assert!(self.contains(index), "Hex coordinate out of bounds");
+        let index = self.to_coord(index);
+        &mut self.cells[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::grid::{Colour, WordSearch};
+
+    #[test]
+    fn test_distance() {
+        let origin = HexCoord::default();
+        assert_eq!(origin.distance(origin), 0);
+        assert_eq!(origin.distance(HexCoord::new(3, 0)), 3);
+        assert_eq!(origin.distance(HexCoord::new(2, -3)), 3);
+        assert_eq!(HexCoord::new(-1, 2).distance(HexCoord::new(2, -2)), 4);
+        assert_eq!(
+            HexCoord::from_cube(1, -3, 2).distance(HexCoord::from_cube(-2, 1, 1)),
+            4
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let centre = HexCoord::new(2, -1);
+        let neighbours = centre.neighbours();
+        assert!(neighbours.iter().all(|&hex| centre.distance(hex) == 1));
+        assert_eq!(neighbours[0], HexCoord::new(3, -1));
+        assert_eq!(neighbours[3], HexCoord::new(1, -1));
+
+        let grid = HexGrid::new(1, 0);
+        assert_eq!(grid.neighbours(HexCoord::default()).count(), 6);
+        assert_eq!(grid.neighbours(HexCoord::new(1, 0)).count(), 3);
+    }
+
+    #[test]
+    fn test_ring() {
+        let centre = HexCoord::new(1, 1);
+        assert_eq!(centre.ring(0).collect::<Vec<_>>(), [centre]);
+        for radius in 1..=4 {
+            let ring: Vec<_> = centre.ring(radius).collect();
+            assert_eq!(ring.len(), 6 * radius);
+            assert!(ring.iter().all(|&hex| centre.distance(hex) == radius));
+            assert!(ring
+                .iter()
+                .zip(ring.iter().cycle().skip(1))
+                .all(|(&a, &b)| a.distance(b) == 1));
+        }
+        assert_eq!(HexGrid::new(3, 0).enumerate_cells().count(), 37);
+    }
+
+    #[test]
+    fn test_layout_renders_with_renderer() {
+        let mut grid = HexGrid::new(1, b'.');
+        grid[HexCoord::new(1, -1)] = b'#';
+        let layout = grid.layout();
+        let rendered = layout
+            .render(|cell| cell.map_or(' ', char::from))
+            .overlay([grid.layout_coord(HexCoord::default())], '@', None)
+            .to_string();
+        assert_eq!(rendered, " . # \n. @ .\n . . \n");
+    }
+
+    #[test]
+    fn test_layout_rendering_path() {
+        let mut grid = HexGrid::new(2, 0_u8);
+        for (i, hex) in HexCoord::default().spiral(2).enumerate() {
+            grid[hex] = u8::try_from(i % 10).unwrap();
+        }
+        let layout = grid.layout();
+        assert_eq!((layout.height(), layout.width()), (5, 9));
+        for (hex, &cell) in grid.enumerate_cells() {
+            assert_eq!(layout[grid.layout_coord(hex)], Some(cell));
+        }
+        assert_eq!(
+            layout
+                .enumerate_cells()
+                .filter(|(_, cell)| cell.is_some())
+                .count(),
+            grid.area()
+        );
+
+        let glyph = |cell: &Option<u8>| cell.map_or(' ', |digit| char::from(b'0' + digit));
+        assert_eq!(
+            layout.render(glyph).to_string(),
+            "  5 4 3  \n 6 5 4 2 \n7 6 0 3 1\n 8 1 2 0 \n  7 8 9  \n"
+        );
+
+        let ring = HexCoord::default()
+            .ring(1)
+            .map(|hex| grid.layout_coord(hex));
+        let coloured = layout
+            .render(glyph)
+            .colour_by(|cell| cell.map(|_| Colour::Blue))
+            .overlay(ring, '*', Some(Colour::Red))
+            .to_string();
+        assert_eq!(
+            coloured.lines().nth(2),
+            Some("\x1b[34m7\x1b[0m \x1b[31m*\x1b[0m \x1b[34m0\x1b[0m \x1b[31m*\x1b[0m \x1b[34m1\x1b[0m")
+        );
+    }
+
+    #[test]
+    fn test_word_search_along_hex_lines() {
+        let mut grid = HexGrid::new(1, b' ');
+        for (hex, letter) in [
+            HexCoord::new(-1, 1),
+            HexCoord::default(),
+            HexCoord::new(1, -1),
+        ]
+        .into_iter()
+        .zip(*b"HEX")
+        {
+            grid[hex] = letter;
+        }
+        let directions = HexDirection::ALL.map(Direction::from);
+        let matches = WordSearch::new(&["HEX"]).find(grid.as_grid(), &directions);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(grid.to_hex(matches[0].start), HexCoord::new(-1, 1));
+        assert_eq!(
+            matches[0].direction,
+            Direction::from(HexDirection::NorthEast)
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use super::Grid;
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+pub type Rgb = (u8, u8, u8);
+
+const MAX_LINE_LEN: usize = 70;
+
+impl<T: Copy + PartialEq> Grid<T> {
+    pub fn write_pbm<W, F>(&self, writer: &mut W, is_set: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> bool,
+    {
+        writeln!(writer, "P1\n{} {}", self.width(), self.height())?;
+        self.write_rows(writer, |cell| [u8::from(is_set(cell))])
+    }
+
+    pub fn write_pgm<W, F>(&self, writer: &mut W, shade: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> u8,
+    {
+        writeln!(writer, "P2\n{} {}\n255", self.width(), self.height())?;
+        self.write_rows(writer, |cell| [shade(cell)])
+    }
+
+    pub fn write_ppm<W, F>(&self, writer: &mut W, colour: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> Rgb,
+    {
+        writeln!(writer, "P3\n{} {}\n255", self.width(), self.height())?;
+        self.write_rows(writer, |cell| {
+            let (r, g, b) = colour(cell);
+            [r, g, b]
+        })
+    }
+
+    pub fn save_pbm<F: Fn(&T) -> bool>(&self, path: impl AsRef<Path>, is_set: F) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_pbm(&mut writer, is_set)?;
+        writer.flush()
+    }
+
+    pub fn save_pgm<F: Fn(&T) -> u8>(&self, path: impl AsRef<Path>, shade: F) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_pgm(&mut writer, shade)?;
+        writer.flush()
+    }
+
+    pub fn save_ppm<F: Fn(&T) -> Rgb>(&self, path: impl AsRef<Path>, colour: F) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_ppm(&mut writer, colour)?;
+        writer.flush()
+    }
+
+    fn write_rows<W, F, const N: usize>(&self, writer: &mut W, pixel: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> [u8; N],
+    {
+        for r in 0..self.height() {
+            let mut line_len = 0;
+            for value in self.row(r).iter().flat_map(&pixel) {
+                let mut digits = [0; 3];
+                let start = if value >= 100 {
+                    0
+                } else if value >= 10 {
+                    1
+                } else {
+                    2
+                };
+                let mut rest = value;
+                for digit in digits[start..].iter_mut().rev() {
+                    *digit = b'0' + rest % 10;
+                    rest /= 10;
+                }
+                let token = &digits[start..];
+
+                if line_len > 0 && line_len + 1 + token.len() > MAX_LINE_LEN {
+                    writer.write_all(b"\n")?;
+                    line_len = 0;
+                }
+                if line_len > 0 {
+                    writer.write_all(b" ")?;
+                    line_len += 1;
+                }
+                writer.write_all(token)?;
+                line_len += token.len();
+            }
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FrameWriter {
+    dir: PathBuf,
+    prefix: String,
+    next_frame: usize,
+}
+
+impl FrameWriter {
+    pub fn new(dir: impl Into<PathBuf>, prefix: &str) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            prefix: prefix.to_owned(),
+            next_frame: 0,
+        })
+    }
+
+    pub fn frames_written(&self) -> usize {
+        self.next_frame
+    }
+
+    pub fn write_pbm<T, F>(&mut self, grid: &Grid<T>, is_set: F) -> io::Result<PathBuf>
+    where
+        T: Copy + PartialEq,
+        F: Fn(&T) -> bool,
+    {
+        let path = self.next_path("pbm");
+        grid.save_pbm(&path, is_set)?;
+        Ok(path)
+    }
+
+    pub fn write_pgm<T, F>(&mut self, grid: &Grid<T>, shade: F) -> io::Result<PathBuf>
+    where
+        T: Copy + PartialEq,
+        F: Fn(&T) -> u8,
+    {
+        let path = self.next_path("pgm");
+        grid.save_pgm(&path, shade)?;
+        Ok(path)
+    }
+
+    pub fn write_ppm<T, F>(&mut self, grid: &Grid<T>, colour: F) -> io::Result<PathBuf>
+    where
+        T: Copy + PartialEq,
+        F: Fn(&T) -> Rgb,
+    {
+        let path = self.next_path("ppm");
+        grid.save_ppm(&path, colour)?;
+        Ok(path)
+    }
+
+    fn next_path(&mut self, extension: &str) -> PathBuf {
+        let path = self.dir.join(format!(
+            "{}_{:05}.{extension}",
+            self.prefix, self.next_frame
+        ));
+        self.next_frame += 1;
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_pgm() {
+        let grid = Grid::from("ab\nc.");
+        let mut bytes = vec![];
+        grid.write_pgm(&mut bytes, |&cell| cell).unwrap();
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            "P2\n2 2\n255\n97 98\n99 46\n"
+        );
+    }
+
+    #[test]
+    fn test_lines_wrap_at_70_characters() {
+        let grid = Grid::new(2, 30, 0_u8);
+        let mut bytes = vec![];
+        grid.write_ppm(&mut bytes, |_| (255, 7, 42)).unwrap();
+        let text = String::from_utf8(bytes).unwrap();
+        let lines: Vec<&str> = text.lines().skip(3).collect();
+        assert!(lines.iter().all(|line| line.len() <= MAX_LINE_LEN));
+        assert!(lines
+            .iter()
+            .all(|line| !line.starts_with(' ') && !line.ends_with(' ')));
+        let values: Vec<&str> = lines.iter().flat_map(|line| line.split(' ')).collect();
+        assert_eq!(values.len(), 2 * 30 * 3);
+        assert!(values.chunks(3).all(|rgb| rgb == ["255", "7", "42"]));
+    }
+}
@@ -1,14 +1,41 @@
 #![allow(dead_code)]
-#![allow(clippy::cast_possible_wrap)]
-#![allow(clippy::cast_sign_loss)]
 
+mod bit_grid;
 mod coord;
 mod direction;
 #[allow(clippy::module_inception)]
 mod grid;
-mod robot;
+mod hex;
+mod image;
+mod point;
+mod record;
+mod render;
+mod scalar;
+mod search;
+mod simulation;
+mod sparse_grid;
+mod wrapping_grid;
 
-pub use coord::Coord;
+pub use bit_grid::BitGrid;
+#[allow(unused_imports)]
+pub use coord::Line;
+pub use coord::{Coord, Coord2};
 pub use direction::Direction;
 pub use grid::Grid;
-pub use robot::Robot;
+#[allow(unused_imports)]
+pub use hex::{HexCoord, HexDirection, HexGrid};
+pub use image::{FrameWriter, Rgb};
+#[allow(unused_imports)]
+pub use point::{Coord3, Point};
+pub use record::{Frame, Recording};
+pub use render::Colour;
+#[allow(unused_imports)]
+pub use render::Renderer;
+pub use scalar::Scalar;
+#[allow(unused_imports)]
+pub use search::{Orientation, StencilMatch, WordMatch};
+pub use search::{Stencil, WordSearch};
+pub use simulation::{Action, Agent, Edges, Simulation};
+pub use sparse_grid::SparseGrid;
+#[allow(unused_imports)]
+pub use wrapping_grid::WrappingGrid;
@@ -0,0 +1,238 @@
+use super::Scalar;
+use std::ops::{Add, AddAssign, Div, Index, IndexMut, Mul, Neg, Rem, Sub, SubAssign};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point<T: Scalar, const N: usize>(pub [T; N]);
+
+pub type Coord3 = Point<isize, 3>;
+
+impl<T: Scalar, const N: usize> Point<T, N> {
+    pub fn new(components: [T; N]) -> Self {
+        Self(components)
+    }
+
+    pub fn origin() -> Self {
+        Self([T::ZERO; N])
+    }
+
+    pub fn components(&self) -> [T; N] {
+        self.0
+    }
+
+    pub fn taxicab_distance(&self, rhs: Self) -> usize {
+        self.0.iter().zip(rhs.0).map(|(&a, b)| a.abs_diff(b)).sum()
+    }
+
+    pub fn chebyshev_distance(&self, rhs: Self) -> usize {
+        self.0
+            .iter()
+            .zip(rhs.0)
+            .map(|(&a, b)| a.abs_diff(b))
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn squared_euclidean_distance(&self, rhs: Self) -> usize {
+        self.0
+            .iter()
+            .zip(rhs.0)
+            .map(|(&a, b)| a.abs_diff(b).pow(2))
+            .sum()
+    }
+
+    pub fn orthogonal_neighbours(self) -> impl Iterator<Item = Self> {
+        (0..N).flat_map(move |axis| {
+            [-T::ONE, T::ONE].map(|delta| {
+                let mut neighbour = self;
+                neighbour.0[axis] += delta;
+                neighbour
+            })
+        })
+    }
+
+    pub fn all_neighbours(self) -> impl Iterator<Item = Self> {
+        let count = 3_usize.pow(N as u32);
+        (0..count)
+            .filter(move |&i| i != count / 2)
+            .map(move |mut i| {
+                let mut neighbour = self;
+                for component in &mut neighbour.0 {
+                    match i % 3 {
+                        0 => *component -= T::ONE,
+                        2 => *component += T::ONE,
+                        _ => (),
+                    }
+                    i /= 3;
+                }
+                neighbour
+            })
+    }
+}
+
+impl<T: Scalar> Point<T, 3> {
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> T {
+        self.0[2]
+    }
+}
+
+impl<T: Scalar, const N: usize> Default for Point<T, N> {
+    fn default() -> Self {
+        Self::origin()
+    }
+}
+
+impl<T: Scalar, const N: usize> Index<usize> for Point<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<T: Scalar, const N: usize> IndexMut<usize> for Point<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<T: Scalar, const N: usize> Add for Point<T, N> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<T: Scalar, const N: usize> AddAssign for Point<T, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a += b;
+        }
+    }
+}
+
+impl<T: Scalar, const N: usize> Sub for Point<T, N> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<T: Scalar, const N: usize> SubAssign for Point<T, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a -= b;
+        }
+    }
+}
+
+impl<T: Scalar, const N: usize> Neg for Point<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(self.0.map(|a| -a))
+    }
+}
+
+impl<T: Scalar, const N: usize> Mul<T> for Point<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self(self.0.map(|a| a * rhs))
+    }
+}
+
+impl<T: Scalar, const N: usize> Div<T> for Point<T, N> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Self(self.0.map(|a| a / rhs))
+    }
+}
+
+impl<T: Scalar, const N: usize> Rem<T> for Point<T, N> {
+    type Output = Self;
+
+    fn rem(self, rhs: T) -> Self::Output {
+        Self(self.0.map(|a| a % rhs))
+    }
+}
+
+impl<T: Scalar, const N: usize> From<[T; N]> for Point<T, N> {
+    fn from(value: [T; N]) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Scalar> From<(T, T, T)> for Point<T, 3> {
+    fn from(value: (T, T, T)) -> Self {
+        Self([value.0, value.1, value.2])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_neighbours() {
+        let centre = Coord3::new([4, -2, 7]);
+        let orthogonal: HashSet<_> = centre.orthogonal_neighbours().collect();
+        assert_eq!(orthogonal.len(), 6);
+        assert!(orthogonal.iter().all(|&n| centre.taxicab_distance(n) == 1));
+
+        let all: Vec<_> = centre.all_neighbours().collect();
+        assert_eq!(all.len(), 26);
+        assert_eq!(all.iter().collect::<HashSet<_>>().len(), 26);
+        assert!(!all.contains(&centre));
+        assert!(all.iter().all(|&n| centre.chebyshev_distance(n) == 1));
+        assert!(orthogonal.iter().all(|n| all.contains(n)));
+
+        assert_eq!(Point::<i32, 2>::origin().all_neighbours().count(), 8);
+        assert_eq!(Point::<i64, 4>::origin().all_neighbours().count(), 80);
+    }
+
+    #[test]
+    fn test_distances() {
+        let (a, b) = (Coord3::new([1, -2, 3]), Coord3::new([-3, 4, 3]));
+        assert_eq!(a.taxicab_distance(b), 10);
+        assert_eq!(a.chebyshev_distance(b), 6);
+        assert_eq!(a.squared_euclidean_distance(b), 52);
+        assert_eq!(b.squared_euclidean_distance(a), 52);
+        assert_eq!(a.taxicab_distance(a), 0);
+        assert_eq!(a.chebyshev_distance(a), 0);
+        assert_eq!(a.squared_euclidean_distance(a), 0);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let (a, b) = (Coord3::from((1, -2, 3)), Coord3::new([-3, 4, 5]));
+        assert_eq!(a + b, Coord3::new([-2, 2, 8]));
+        assert_eq!(a - b, Coord3::new([4, -6, -2]));
+        assert_eq!(-a, Coord3::new([-1, 2, -3]));
+        assert_eq!(b * 3, Coord3::new([-9, 12, 15]));
+        assert_eq!(b / 2, Coord3::new([-1, 2, 2]));
+        assert_eq!(b % 2, Coord3::new([-1, 0, 1]));
+        assert_eq!((a.x(), a.y(), a.z()), (1, -2, 3));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        c[2] = 0;
+        assert_eq!(c.components(), [-3, 4, 0]);
+        assert_eq!(Coord3::default(), Coord3::origin());
+    }
+}
@@ -0,0 +1,406 @@
+use super::{Coord, Grid};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    time::Duration,
+};
+
+#[derive(Debug, Clone)]
+pub enum Frame {
+    Key(Grid<u8>),
+    Diff(Vec<(Coord, u8)>),
+}
+
+impl Frame {
+    pub fn apply_to(&self, grid: &mut Grid<u8>) {
+        match self {
+            Frame::Key(key) => *grid = key.clone(),
+            Frame::Diff(cells) => {
+                for &(coord, cell) in cells {
+                    grid[coord] = cell;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Recording {
+    height: usize,
+    width: usize,
+    interval: Duration,
+    keyframe_interval: usize,
+    frames: Vec<Frame>,
+    last: Option<Grid<u8>>,
+}
+
+impl Recording {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            height: 0,
+            width: 0,
+            interval,
+            keyframe_interval: 256,
+            frames: Vec::new(),
+            last: None,
+        }
+    }
+
+    pub fn with_keyframe_interval(mut self, keyframe_interval: usize) -> Self {
+        self.keyframe_interval = keyframe_interval.max(1);
+        self
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn capture(&mut self, grid: &Grid<u8>) {
+        let frame = match &self.last {
+            Some(last)
+                if !self.frames.len().is_multiple_of(self.keyframe_interval)
+                    && (last.height(), last.width()) == (grid.height(), grid.width()) =>
+            {
+                Frame::Diff(
+                    grid.enumerate_cells()
+                        .filter(|&(coord, &cell)| last[coord] != cell)
+                        .map(|(coord, &cell)| (coord, cell))
+                        .collect(),
+                )
+            }
+            _ => Frame::Key(grid.clone()),
+        };
+        self.push(frame);
+        self.last = Some(grid.clone());
+    }
+
+    pub fn snapshot(&self, index: usize) -> Option<Grid<u8>> {
+        let index = index.min(self.frames.len().checked_sub(1)?);
+        let key = self.frames[..=index]
+            .iter()
+            .rposition(|frame| matches!(frame, Frame::Key(_)))?;
+        let mut grid = Grid::new(self.height, self.width, b' ');
+        for frame in &self.frames[key..=index] {
+            frame.apply_to(&mut grid);
+        }
+        Some(grid)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut writer = BufWriter::new(File::create(path)?);
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "cast")
+        {
+            self.write_asciicast(&mut writer)?;
+        } else {
+            self.write_frame_log(&mut writer)?;
+        }
+        writer.flush()
+    }
+
+    pub fn write_asciicast<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(
+            writer,
+            r#"{{"version": 2, "width": {}, "height": {}}}"#,
+            self.width,
+            self.height + 1
+        )?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            let mut data = String::new();
+            match frame {
+                Frame::Key(grid) => {
+                    data.push_str("\x1b[2J\x1b[H");
+                    for r in 0..grid.height() {
+                        data.extend(grid.row(r).iter().map(|&cell| cell as char));
+                        data.push_str("\r\n");
+                    }
+                }
+                Frame::Diff(cells) => {
+                    for &(coord, cell) in cells {
+                        data.push_str(&format!(
+                            "\x1b[{};{}H{}",
+                            coord.r + 1,
+                            coord.c + 1,
+                            cell as char
+                        ));
+                    }
+                    data.push_str(&format!("\x1b[{};1H", self.height + 1));
+                }
+            }
+            let time = self.interval.as_secs_f64() * i as f64;
+            writeln!(writer, r#"[{time:.6}, "o", "{}"]"#, escape_json(&data))?;
+        }
+        Ok(())
+    }
+
+    pub fn write_frame_log<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(
+            writer,
+            "framelog 1 {} {} {}",
+            self.height,
+            self.width,
+            self.interval.as_millis()
+        )?;
+        for frame in &self.frames {
+            match frame {
+                Frame::Key(grid) => {
+                    writeln!(writer, "K")?;
+                    for r in 0..grid.height() {
+                        writeln!(writer, "{}", escape_row(grid.row(r)))?;
+                    }
+                }
+                Frame::Diff(cells) => {
+                    write!(writer, "D")?;
+                    for &(coord, cell) in cells {
+                        write!(writer, " {},{},{}", coord.r, coord.c, cell)?;
+                    }
+                    writeln!(writer)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn load_frame_log(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read_frame_log(BufReader::new(File::open(path)?))
+    }
+
+    pub fn read_frame_log<R: BufRead>(reader: R) -> io::Result<Self> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_owned());
+        let mut lines = reader.lines();
+
+        let header = lines
+            .next()
+            .ok_or_else(|| invalid("Frame log is empty"))??;
+        let fields: Vec<&str> = header.split(' ').collect();
+        let [_, _, height, width, interval] = fields[..] else {
+            return Err(invalid("Frame log header is malformed"));
+        };
+        if !header.starts_with("framelog 1 ") {
+            return Err(invalid("Unsupported frame log version"));
+        }
+        let parse_field = |field: &str| {
+            field
+                .parse::<u64>()
+                .map_err(|_| invalid("Frame log header should contain integers"))
+        };
+        let (height, width) = (parse_field(height)? as usize, parse_field(width)? as usize);
+        let mut recording = Recording::new(Duration::from_millis(parse_field(interval)?));
+        recording.height = height;
+        recording.width = width;
+
+        while let Some(line) = lines.next() {
+            let line = line?;
+            if line == "K" {
+                let mut grid = Grid::new(height, width, b' ');
+                for r in 0..height {
+                    let row = lines
+                        .next()
+                        .ok_or_else(|| invalid("Keyframe is truncated"))??;
+                    let row = unescape_row(&row)
+                        .ok_or_else(|| invalid("Keyframe row has an invalid escape"))?;
+                    if row.len() != width {
+                        return Err(invalid("Keyframe row has the wrong width"));
+                    }
+                    for (c, cell) in row.into_iter().enumerate() {
+                        let coord = Coord::from_usize(r, c)
+                            .ok_or_else(|| invalid("Keyframe is too large"))?;
+                        grid[coord] = cell;
+                    }
+                }
+                recording.frames.push(Frame::Key(grid));
+            } else if let Some(cells) = line.strip_prefix('D') {
+                let parse_cell = |cell: &str| {
+                    let mut parts = cell.split(',');
+                    let r: usize = parts.next()?.parse().ok()?;
+                    let c: usize = parts.next()?.parse().ok()?;
+                    let b: u8 = parts.next()?.parse().ok()?;
+                    if parts.next().is_some() || r >= height || c >= width {
+                        return None;
+                    }
+                    Some((Coord::from_usize(r, c)?, b))
+                };
+                let cells = cells
+                    .split_whitespace()
+                    .map(|cell| {
+                        parse_cell(cell)
+                            .ok_or_else(|| invalid("Diff cell should be r,c,byte within bounds"))
+                    })
+                    .collect::<io::Result<_>>()?;
+                recording.frames.push(Frame::Diff(cells));
+            } else {
+                return Err(invalid("Frame should start with K or D"));
+            }
+        }
+
+        if matches!(recording.frames.first(), Some(Frame::Diff(_))) {
+            return Err(invalid("Frame log should start with a keyframe"));
+        }
+        Ok(recording)
+    }
+
+    fn push(&mut self, frame: Frame) {
+        if let Frame::Key(grid) = &frame {
+            self.height = grid.height();
+            self.width = grid.width();
+        }
+        self.frames.push(frame);
+    }
+}
+
+fn escape_json(data: &str) -> String {
+    let mut escaped = String::with_capacity(data.len());
+    for ch in data.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if ch.is_control() => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn escape_row(row: &[u8]) -> String {
+    let mut escaped = String::with_capacity(row.len());
+    for &cell in row {
+        match cell {
+            b'\\' => escaped.push_str("\\\\"),
+            b' '..=b'~' => escaped.push(cell as char),
+            _ => escaped.push_str(&format!("\\x{cell:02x}")),
+        }
+    }
+    escaped
+}
+
+fn unescape_row(row: &str) -> Option<Vec<u8>> {
+    let mut bytes = row.bytes();
+    let mut unescaped = Vec::with_capacity(row.len());
+    while let Some(byte) = bytes.next() {
+        if byte != b'\\' {
+            unescaped.push(byte);
+            continue;
+        }
+        match bytes.next()? {
+            b'\\' => unescaped.push(b'\\'),
+            b'x' => {
+                let hex = [bytes.next()?, bytes.next()?];
+                unescaped.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+            }
+            _ => return None,
+        }
+    }
+    Some(unescaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording() -> Recording {
+        let mut recording = Recording::new(Duration::from_millis(40)).with_keyframe_interval(3);
+        let mut grid = Grid::from("ab\\\n.#~");
+        for i in 0..5u8 {
+            grid[Coord::new(1, 0)] = b'0' + i;
+            if i == 2 {
+                grid[Coord::new(0, 1)] = b'\n';
+            }
+            recording.capture(&grid);
+        }
+        recording
+    }
+
+    #[test]
+    fn test_frame_log_round_trip() {
+        let recording = recording();
+        let mut bytes = vec![];
+        recording.write_frame_log(&mut bytes).unwrap();
+        let text = String::from_utf8(bytes.clone()).unwrap();
+        assert!(text.starts_with("framelog 1 2 3 40\nK\nab\\\\\n0#~\nD 1,0,49\n"));
+        assert!(text.contains("a\\x0a\\\\"), "{text}");
+
+        let loaded = Recording::read_frame_log(bytes.as_slice()).unwrap();
+        assert_eq!((loaded.height(), loaded.width()), (2, 3));
+        assert_eq!(loaded.interval(), recording.interval());
+        assert_eq!(loaded.len(), recording.len());
+        for i in 0..recording.len() {
+            assert_eq!(
+                loaded.snapshot(i).unwrap().to_string(),
+                recording.snapshot(i).unwrap().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_frame_log_rejects_malformed_input() {
+        let error = |log: &str| {
+            Recording::read_frame_log(log.as_bytes())
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(error(""), "Frame log is empty");
+        assert_eq!(error("framelog 2 1 1 40"), "Unsupported frame log version");
+        assert_eq!(
+            error("framelog 1 1 2 40\nK\na"),
+            "Keyframe row has the wrong width"
+        );
+        assert_eq!(
+            error("framelog 1 1 1 40\nK\n\\q"),
+            "Keyframe row has an invalid escape"
+        );
+        assert_eq!(
+            error("framelog 1 1 1 40\nK\n.\nD 1,0,46"),
+            "Diff cell should be r,c,byte within bounds"
+        );
+        assert_eq!(
+            error("framelog 1 1 1 40\nD 0,0,46"),
+            "Frame log should start with a keyframe"
+        );
+    }
+
+    #[test]
+    fn test_snapshot_seeks_from_the_nearest_keyframe() {
+        let recording = recording();
+        assert!(matches!(recording.frames()[3], Frame::Key(_)));
+        assert!(matches!(recording.frames()[4], Frame::Diff(_)));
+        assert_eq!(recording.snapshot(0).unwrap().to_string(), "ab\\\n0#~\n");
+        assert_eq!(recording.snapshot(2).unwrap().row(1), b"2#~");
+        assert_eq!(recording.snapshot(4).unwrap().row(1), b"4#~");
+        assert_eq!(recording.snapshot(99).unwrap().row(1), b"4#~");
+        assert!(Recording::new(Duration::ZERO).snapshot(0).is_none());
+    }
+
+    #[test]
+    fn test_escape_json() {
+        assert_eq!(
+            escape_json("\"a\\b\"\r\n\t\x1b[H"),
+            "\\\"a\\\\b\\\"\\r\\n\\t\\u001b[H"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use super::{Coord, Direction, Grid};
+use std::{collections::HashMap, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Colour {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl Colour {
+    fn ansi_code(self) -> u8 {
+        let code = self as u8;
+        if code < 8 {
+            30 + code
+        } else {
+            90 + code - 8
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Mark {
+    glyph: char,
+    colour: Option<Colour>,
+}
+
+type GlyphFn<'a, T> = Box<dyn Fn(&T) -> char + 'a>;
+type ColourFn<'a, T> = Box<dyn Fn(&T) -> Option<Colour> + 'a>;
+
+pub struct Renderer<'a, T: Copy + PartialEq> {
+    grid: &'a Grid<T>,
+    glyph: GlyphFn<'a, T>,
+    colour: Option<ColourFn<'a, T>>,
+    overlays: HashMap<Coord, Mark>,
+    rulers: bool,
+}
+
+impl<T: Copy + PartialEq> Grid<T> {
+    pub fn render<'a, F>(&'a self, glyph: F) -> Renderer<'a, T>
+    where
+        F: Fn(&T) -> char + 'a,
+    {
+        Renderer {
+            grid: self,
+            glyph: Box::new(glyph),
+            colour: None,
+            overlays: HashMap::new(),
+            rulers: false,
+        }
+    }
+}
+
+impl<'a, T: Copy + PartialEq> Renderer<'a, T> {
+    pub fn colour_by<F>(mut self, colour: F) -> Self
+    where
+        F: Fn(&T) -> Option<Colour> + 'a,
+    {
+        self.colour = Some(Box::new(colour));
+        self
+    }
+
+    pub fn overlay<I>(mut self, coords: I, glyph: char, colour: Option<Colour>) -> Self
+    where
+        I: IntoIterator<Item = Coord>,
+    {
+        for coord in coords {
+            self.overlays.insert(coord, Mark { glyph, colour });
+        }
+        self
+    }
+
+    pub fn path(mut self, path: &[Coord], end_glyph: char, colour: Option<Colour>) -> Self {
+        for (i, &coord) in path.iter().enumerate() {
+            let glyph = path
+                .get(i + 1)
+                .and_then(|&next| Direction::try_from(next - coord).ok())
+                .map(char::from)
+                .unwrap_or(end_glyph);
+            self.overlays.insert(coord, Mark { glyph, colour });
+        }
+        self
+    }
+
+    pub fn rulers(mut self, rulers: bool) -> Self {
+        self.rulers = rulers;
+        self
+    }
+
+    fn mark_at(&self, coord: Coord) -> Mark {
+        self.overlays.get(&coord).copied().unwrap_or_else(|| {
+            let cell = &self.grid[coord];
+            Mark {
+                glyph: (self.glyph)(cell),
+                colour: self.colour.as_ref().and_then(|colour| colour(cell)),
+            }
+        })
+    }
+
+    fn write_column_ruler(&self, f: &mut fmt::Formatter<'_>, margin: usize) -> fmt::Result {
+        let width = self.grid.width();
+        let digits = width.saturating_sub(1).max(1).ilog10() + 1;
+        for place in (0..digits).rev() {
+            write!(f, "{:margin$}", "")?;
+            for c in 0..width {
+                let digit = (c / 10_usize.pow(place)) % 10;
+                if place > 0 && c < 10_usize.pow(place) {
+                    write!(f, " ")?;
+                } else {
+                    write!(f, "{digit}")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Copy + PartialEq> fmt::Display for Renderer<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let row_digits = (self.grid.height().saturating_sub(1).max(1).ilog10() + 1) as usize;
+        let margin = if self.rulers { row_digits + 1 } else { 0 };
+        if self.rulers {
+            self.write_column_ruler(f, margin)?;
+        }
+
+        for r in 0..self.grid.height() {
+            if self.rulers {
+                write!(f, "{r:>row_digits$} ")?;
+            }
+            let mut active = None;
+            for c in 0..self.grid.width() {
+                let coord = Coord::from_usize(r, c).expect("Grid cell should fit in a coordinate");
+                let Mark { glyph, colour } = self.mark_at(coord);
+                if colour != active {
+                    match colour {
+                        Some(colour) => write!(f, "\x1b[{}m", colour.ansi_code())?,
+                        None => write!(f, "\x1b[0m")?,
+                    }
+                    active = colour;
+                }
+                write!(f, "{glyph}")?;
+            }
+            if active.is_some() {
+                write!(f, "\x1b[0m")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign},
+};
+
+pub trait Scalar:
+    Copy
+    + Debug
+    + Default
+    + Eq
+    + Ord
+    + Hash
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Mul<Output = Self>
+    + MulAssign
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+    fn abs_diff(self, rhs: Self) -> usize;
+    fn from_usize(value: usize) -> Option<Self>;
+    fn to_usize(self) -> Option<usize>;
+
+    fn gcd(self, rhs: Self) -> Self {
+        let (mut a, mut b) = (self.abs(), rhs.abs());
+        while b != Self::ZERO {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+}
+
+macro_rules! impl_scalar {
+    ($($t:ty),*) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+
+                fn signum(self) -> Self {
+                    <$t>::signum(self)
+                }
+
+                fn rem_euclid(self, rhs: Self) -> Self {
+                    <$t>::rem_euclid(self, rhs)
+                }
+
+                fn abs_diff(self, rhs: Self) -> usize {
+                    usize::try_from(<$t>::abs_diff(self, rhs))
+                        .expect("Difference should fit in usize")
+                }
+
+                fn from_usize(value: usize) -> Option<Self> {
+                    Self::try_from(value).ok()
+                }
+
+                fn to_usize(self) -> Option<usize> {
+                    usize::try_from(self).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar!(i32, i64, isize);
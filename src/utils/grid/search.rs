@@ -0,0 +1,241 @@
+use super::{Coord, Direction, Grid};
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WordMatch {
+    pub word: usize,
+    pub start: Coord,
+    pub direction: Direction,
+}
+
+#[derive(Debug, Clone)]
+pub struct WordSearch {
+    words: Vec<Vec<u8>>,
+    transitions: Vec<[u32; 256]>,
+    outputs: Vec<Vec<usize>>,
+}
+
+impl WordSearch {
+    pub fn new<W: AsRef<[u8]>>(words: &[W]) -> Self {
+        let words: Vec<Vec<u8>> = words.iter().map(|w| w.as_ref().to_vec()).collect();
+        assert!(
+            words.iter().all(|word| !word.is_empty()),
+            "Words should not be empty"
+        );
+
+        let mut transitions = vec![[0; 256]];
+        let mut children: Vec<[Option<u32>; 256]> = vec![[None; 256]];
+        let mut outputs = vec![Vec::new()];
+        for (i, word) in words.iter().enumerate() {
+            let mut state = 0;
+            for &b in word {
+                state = match children[state][b as usize] {
+                    Some(next) => next as usize,
+                    None => {
+                        let next = transitions.len();
+                        children[state][b as usize] = Some(next as u32);
+                        transitions.push([0; 256]);
+                        children.push([None; 256]);
+                        outputs.push(Vec::new());
+                        next
+                    }
+                };
+            }
+            outputs[state].push(i);
+        }
+
+        let mut failure = vec![0; transitions.len()];
+        let mut queue = VecDeque::from([0]);
+        while let Some(state) = queue.pop_front() {
+            for b in 0..256 {
+                match children[state][b] {
+                    Some(child) => {
+                        let child = child as usize;
+                        failure[child] = if state == 0 {
+                            0
+                        } else {
+                            transitions[failure[state]][b] as usize
+                        };
+                        let inherited = outputs[failure[child]].clone();
+                        outputs[child].extend(inherited);
+                        transitions[state][b] = child as u32;
+                        queue.push_back(child);
+                    }
+                    None if state != 0 => transitions[state][b] = transitions[failure[state]][b],
+                    None => {}
+                }
+            }
+        }
+
+        Self {
+            words,
+            transitions,
+            outputs,
+        }
+    }
+
+    pub fn word(&self, index: usize) -> &[u8] {
+        &self.words[index]
+    }
+
+    pub fn find(&self, grid: &Grid<u8>, directions: &[Direction]) -> Vec<WordMatch> {
+        let mut matches = Vec::new();
+        for &direction in directions {
+            let step = Coord::from(direction);
+            let ray_starts = grid
+                .enumerate_cells()
+                .map(|(coord, _)| coord)
+                .filter(|&coord| !grid.contains(coord - step));
+            for ray_start in ray_starts {
+                let mut state = 0;
+                for coord in grid.ray(ray_start, step) {
+                    state = self.transitions[state][grid[coord] as usize] as usize;
+                    matches.extend(self.outputs[state].iter().map(|&word| {
+                        let len = isize::try_from(self.words[word].len())
+                            .expect("Word length should fit in isize");
+                        WordMatch {
+                            word,
+                            start: coord - step * (len - 1),
+                            direction,
+                        }
+                    }));
+                }
+            }
+        }
+        matches
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub rotation: i16,
+    pub reflected: bool,
+}
+
+impl Orientation {
+    fn apply(self, coord: Coord) -> Coord {
+        let coord = if self.reflected {
+            Coord::new(coord.r, -coord.c)
+        } else {
+            coord
+        };
+        (0..self.rotation / 90).fold(coord, |coord, _| Coord::new(coord.c, -coord.r))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StencilMatch {
+    pub origin: Coord,
+    pub orientation: Orientation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stencil {
+    cells: Vec<(Coord, u8)>,
+}
+
+impl Stencil {
+    pub const WILDCARD: u8 = b'?';
+
+    pub fn new(cells: impl IntoIterator<Item = (Coord, u8)>) -> Self {
+        let mut cells: Vec<_> = cells.into_iter().collect();
+        assert!(!cells.is_empty(), "Stencil should have at least one cell");
+        let min_r = cells.iter().map(|(coord, _)| coord.r).min().unwrap_or(0);
+        let min_c = cells.iter().map(|(coord, _)| coord.c).min().unwrap_or(0);
+        for (coord, _) in &mut cells {
+            *coord -= Coord::new(min_r, min_c);
+        }
+        cells.sort_unstable_by_key(|&(coord, _)| (coord.r, coord.c));
+        cells.dedup_by_key(|&mut (coord, _)| coord);
+        Self { cells }
+    }
+
+    pub fn parse(pattern: &str) -> Self {
+        Self::parse_with(pattern, Self::WILDCARD)
+    }
+
+    pub fn parse_with(pattern: &str, wildcard: u8) -> Self {
+        Self::new(pattern.lines().enumerate().flat_map(|(r, line)| {
+            line.bytes()
+                .enumerate()
+                .filter(move |&(_, b)| b != wildcard)
+                .map(move |(c, b)| {
+                    let coord =
+                        Coord::from_usize(r, c).expect("Pattern should fit in a coordinate");
+                    (coord, b)
+                })
+        }))
+    }
+
+    pub fn cross(word: &[u8]) -> Self {
+        assert!(
+            !word.len().is_multiple_of(2),
+            "Cross word should have odd length"
+        );
+        let last = isize::try_from(word.len()).expect("Cross word should fit in isize") - 1;
+        Self::new(
+            (0..)
+                .zip(word)
+                .flat_map(|(i, &b)| [(Coord::new(i, i), b), (Coord::new(i, last - i), b)]),
+        )
+    }
+
+    pub fn plus(word: &[u8]) -> Self {
+        assert!(
+            !word.len().is_multiple_of(2),
+            "Plus word should have odd length"
+        );
+        let mid = isize::try_from(word.len() / 2).expect("Plus word should fit in isize");
+        Self::new(
+            (0..)
+                .zip(word)
+                .flat_map(|(i, &b)| [(Coord::new(i, mid), b), (Coord::new(mid, i), b)]),
+        )
+    }
+
+    pub fn oriented(&self, orientation: Orientation) -> Self {
+        Self::new(
+            self.cells
+                .iter()
+                .map(|&(coord, b)| (orientation.apply(coord), b)),
+        )
+    }
+
+    pub fn orientations(&self) -> Vec<(Orientation, Stencil)> {
+        let mut orientations: Vec<(Orientation, Stencil)> = Vec::new();
+        for reflected in [false, true] {
+            for rotation in [0, 90, 180, 270] {
+                let orientation = Orientation {
+                    rotation,
+                    reflected,
+                };
+                let stencil = self.oriented(orientation);
+                if orientations.iter().all(|(_, other)| *other != stencil) {
+                    orientations.push((orientation, stencil));
+                }
+            }
+        }
+        orientations
+    }
+
+    pub fn matches_at(&self, grid: &Grid<u8>, origin: Coord) -> bool {
+        self.cells
+            .iter()
+            .all(|&(offset, b)| grid.get(origin + offset) == Some(&b))
+    }
+
+    pub fn find(&self, grid: &Grid<u8>) -> Vec<StencilMatch> {
+        let orientations = self.orientations();
+        grid.enumerate_cells()
+            .flat_map(|(origin, _)| {
+                orientations
+                    .iter()
+                    .filter(move |(_, stencil)| stencil.matches_at(grid, origin))
+                    .map(move |&(orientation, _)| StencilMatch {
+                        origin,
+                        orientation,
+                    })
+            })
+            .collect()
+    }
+}
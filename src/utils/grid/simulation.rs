@@ -0,0 +1,279 @@
+use super::{BitGrid, Coord, Direction, Grid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Agent {
+    pub pos: Coord,
+    pub dir: Direction,
+    pub vel: Coord,
+    pub active: bool,
+}
+
+impl Agent {
+    pub fn new(pos: Coord, dir: Direction, vel: Coord) -> Self {
+        Self {
+            pos,
+            dir,
+            vel,
+            active: true,
+        }
+    }
+
+    pub fn heading(pos: Coord, dir: Direction) -> Self {
+        Self::new(pos, dir, Coord::from(dir))
+    }
+
+    pub fn moving(pos: Coord, vel: Coord) -> Self {
+        Self::new(pos, Direction::default(), vel)
+    }
+
+    pub fn next_pos(&self) -> Coord {
+        self.pos + self.dir
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Wait,
+    Advance,
+    Forward,
+    Step(Direction),
+    Turn(i16),
+    Despawn,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edges {
+    Wrap,
+    Block,
+    Despawn,
+}
+
+#[derive(Debug, Clone)]
+pub struct Simulation<T: Copy + PartialEq> {
+    world: Grid<T>,
+    agents: Vec<Agent>,
+    edges: Edges,
+    tick: usize,
+}
+
+impl<T: Copy + PartialEq> Simulation<T> {
+    pub fn new(world: Grid<T>, agents: Vec<Agent>, edges: Edges) -> Self {
+        Self {
+            world,
+            agents,
+            edges,
+            tick: 0,
+        }
+    }
+
+    pub fn world(&self) -> &Grid<T> {
+        &self.world
+    }
+
+    pub fn into_world(self) -> Grid<T> {
+        self.world
+    }
+
+    pub fn active_agents(&self) -> impl Iterator<Item = &Agent> {
+        self.agents.iter().filter(|agent| agent.active)
+    }
+
+    pub fn tick(&self) -> usize {
+        self.tick
+    }
+
+    pub fn step<R>(&mut self, mut rule: R)
+    where
+        R: FnMut(&mut Grid<T>, &Agent) -> Action,
+    {
+        for i in 0..self.agents.len() {
+            if !self.agents[i].active {
+                continue;
+            }
+            let action = rule(&mut self.world, &self.agents[i]);
+            self.apply(i, action);
+        }
+        self.tick += 1;
+    }
+
+    pub fn run<R, H>(&mut self, mut rule: R, mut on_tick: H) -> usize
+    where
+        R: FnMut(&mut Grid<T>, &Agent) -> Action,
+        H: FnMut(&Self) -> bool,
+    {
+        let start = self.tick;
+        while self.active_agents().next().is_some() {
+            self.step(&mut rule);
+            if self.active_agents().next().is_none() || !on_tick(self) {
+                break;
+            }
+        }
+        self.tick - start
+    }
+
+    pub fn advance_by(&mut self, ticks: usize) {
+        let steps = isize::try_from(ticks).expect("Tick count should fit in isize");
+        for i in 0..self.agents.len() {
+            if self.agents[i].active {
+                let target = self.agents[i].pos + self.agents[i].vel * steps;
+                self.move_to(i, target);
+            }
+        }
+        self.tick += ticks;
+    }
+
+    pub fn occupancy(&self) -> Grid<usize> {
+        let mut occupancy = Grid::new(self.world.height(), self.world.width(), 0);
+        for agent in self.active_agents() {
+            occupancy[agent.pos] += 1;
+        }
+        occupancy
+    }
+
+    pub fn has_collisions(&self, seen: &mut BitGrid) -> bool {
+        seen.clear();
+        self.active_agents().any(|agent| !seen.insert(agent.pos))
+    }
+
+    fn apply(&mut self, i: usize, action: Action) {
+        let agent = self.agents[i];
+        match action {
+            Action::Wait => (),
+            Action::Advance => self.move_to(i, agent.pos + agent.vel),
+            Action::Forward => self.move_to(i, agent.pos + agent.dir),
+            Action::Step(dir) => {
+                self.agents[i].dir = dir;
+                self.move_to(i, agent.pos + dir);
+            }
+            Action::Turn(degrees) => self.agents[i].dir.rotate(degrees),
+            Action::Despawn => self.agents[i].active = false,
+        }
+    }
+
+    fn move_to(&mut self, i: usize, target: Coord) {
+        if self.world.contains(target) {
+            self.agents[i].pos = target;
+            return;
+        }
+        match self.edges {
+            Edges::Wrap => self.agents[i].pos = self.world.wrap(target),
+            Edges::Block => (),
+            Edges::Despawn => self.agents[i].active = false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world() -> Grid<u8> {
+        Grid::new(3, 4, b'.')
+    }
+
+    #[test]
+    fn test_edges() {
+        let agents = vec![
+            Agent::heading(Coord::new(0, 3), Direction::East),
+            Agent::heading(Coord::new(1, 1), Direction::North),
+        ];
+        let forward = |_: &mut Grid<u8>, _: &Agent| Action::Forward;
+
+        let mut blocked = Simulation::new(world(), agents.clone(), Edges::Block);
+        blocked.step(forward);
+        blocked.step(forward);
+        let positions: Vec<_> = blocked.active_agents().map(|agent| agent.pos).collect();
+        assert_eq!(positions, [Coord::new(0, 3), Coord::new(0, 1)]);
+
+        let mut despawned = Simulation::new(world(), agents.clone(), Edges::Despawn);
+        despawned.step(forward);
+        let positions: Vec<_> = despawned.active_agents().map(|agent| agent.pos).collect();
+        assert_eq!(positions, [Coord::new(0, 1)]);
+
+        let mut wrapped = Simulation::new(world(), agents, Edges::Wrap);
+        wrapped.step(forward);
+        wrapped.step(forward);
+        let positions: Vec<_> = wrapped.active_agents().map(|agent| agent.pos).collect();
+        assert_eq!(positions, [Coord::new(0, 1), Coord::new(2, 1)]);
+        assert_eq!(wrapped.tick(), 2);
+    }
+
+    #[test]
+    fn test_turn_and_step() {
+        let mut sim = Simulation::new(
+            world(),
+            vec![Agent::heading(Coord::new(1, 1), Direction::North)],
+            Edges::Block,
+        );
+        sim.step(|_, _| Action::Turn(90));
+        sim.step(|_, _| Action::Forward);
+        sim.step(|_, _| Action::Turn(-135));
+        sim.step(|_, _| Action::Forward);
+        sim.step(|_, _| Action::Step(Direction::South));
+        let agent = sim.active_agents().next().unwrap();
+        assert_eq!((agent.pos, agent.dir), (Coord::new(1, 1), Direction::South));
+    }
+
+    #[test]
+    fn test_run_terminates() {
+        let agents = vec![
+            Agent::heading(Coord::new(0, 0), Direction::East),
+            Agent::heading(Coord::new(2, 0), Direction::East),
+        ];
+        let mut sim = Simulation::new(world(), agents, Edges::Despawn);
+        let ticks = sim.run(
+            |world, agent| {
+                world[agent.pos] = b'#';
+                Action::Forward
+            },
+            |_| true,
+        );
+        assert_eq!(ticks, 4);
+        assert_eq!(sim.active_agents().count(), 0);
+        assert_eq!(sim.world().to_string(), "####\n....\n####\n");
+
+        let mut sim = Simulation::new(
+            world(),
+            vec![Agent::heading(Coord::new(0, 0), Direction::East)],
+            Edges::Wrap,
+        );
+        assert_eq!(sim.run(|_, _| Action::Forward, |sim| sim.tick() < 10), 10);
+        assert_eq!(sim.active_agents().next().unwrap().pos, Coord::new(0, 2));
+
+        let mut sim = Simulation::new(
+            world(),
+            vec![Agent::heading(Coord::new(0, 0), Direction::East)],
+            Edges::Wrap,
+        );
+        assert_eq!(sim.run(|_, _| Action::Despawn, |_| true), 1);
+        assert_eq!(sim.run(|_, _| Action::Forward, |_| true), 0);
+        assert_eq!(sim.into_world().to_string(), "....\n....\n....\n");
+    }
+
+    #[test]
+    fn test_collisions_and_occupancy() {
+        let agents = vec![
+            Agent::moving(Coord::new(0, 0), Coord::new(1, 1)),
+            Agent::moving(Coord::new(2, 3), Coord::new(-1, -2)),
+            Agent::moving(Coord::new(1, 0), Coord::new(0, 1)),
+        ];
+        let mut sim = Simulation::new(world(), agents, Edges::Wrap);
+        let mut seen = BitGrid::new(3, 4);
+        assert!(!sim.has_collisions(&mut seen));
+
+        sim.step(|_, _| Action::Advance);
+        assert!(sim.has_collisions(&mut seen));
+        let occupancy = sim.occupancy();
+        assert_eq!(occupancy[Coord::new(1, 1)], 3);
+        assert_eq!(occupancy.into_iter().sum::<usize>(), 3);
+
+        sim.advance_by(4);
+        assert!(!sim.has_collisions(&mut seen));
+        let positions: Vec<_> = sim.active_agents().map(|agent| agent.pos).collect();
+        assert_eq!(
+            positions,
+            [Coord::new(2, 1), Coord::new(0, 1), Coord::new(1, 1)]
+        );
+        assert_eq!(sim.tick(), 5);
+    }
+}
@@ -0,0 +1,152 @@
+use super::{Coord, Grid};
+use std::{collections::HashMap, ops::Index};
+
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T: Copy + PartialEq> {
+    cells: HashMap<Coord, T>,
+    default_value: T,
+}
+
+impl<T: Copy + PartialEq> SparseGrid<T> {
+    pub fn new(default_value: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default_value,
+        }
+    }
+
+    pub fn with_capacity(capacity: usize, default_value: T) -> Self {
+        Self {
+            cells: HashMap::with_capacity(capacity),
+            default_value,
+        }
+    }
+
+    pub fn from_grid(grid: &Grid<T>, default_value: T) -> Self {
+        let cells = grid
+            .enumerate_cells()
+            .filter(|(_, &cell)| cell != default_value)
+            .map(|(coord, &cell)| (coord, cell))
+            .collect();
+        Self {
+            cells,
+            default_value,
+        }
+    }
+
+    pub fn default_value(&self) -> T {
+        self.default_value
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.cells.get(&coord)
+    }
+
+    pub fn is_set(&self, coord: Coord) -> bool {
+        self.cells.contains_key(&coord)
+    }
+
+    pub fn set(&mut self, coord: Coord, value: T) -> Option<T> {
+        if value == self.default_value {
+            self.cells.remove(&coord)
+        } else {
+            self.cells.insert(coord, value)
+        }
+    }
+
+    pub fn remove(&mut self, coord: Coord) -> Option<T> {
+        self.cells.remove(&coord)
+    }
+
+    pub fn enumerate_cells(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.cells.iter().map(|(&coord, cell)| (coord, cell))
+    }
+
+    pub fn position(&self, target: T) -> Option<Coord> {
+        self.enumerate_cells()
+            .find(|(_, cell)| **cell == target)
+            .map(|(coord, _)| coord)
+    }
+
+    pub fn positions(&self, target: T) -> impl Iterator<Item = Coord> + use<'_, T> {
+        self.enumerate_cells()
+            .filter(move |(_, cell)| **cell == target)
+            .map(|(coord, _)| coord)
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    pub fn bounds(&self) -> Option<(Coord, Coord)> {
+        let mut coords = self.cells.keys();
+        let first = *coords.next()?;
+        Some(coords.fold((first, first), |(min, max), &coord| {
+            (
+                Coord::new(min.r.min(coord.r), min.c.min(coord.c)),
+                Coord::new(max.r.max(coord.r), max.c.max(coord.c)),
+            )
+        }))
+    }
+
+    pub fn to_grid(&self) -> Option<(Grid<T>, Coord)> {
+        let (min, max) = self.bounds()?;
+        let (r, c) = (max - min).to_usize()?;
+        let mut grid = Grid::new(r + 1, c + 1, self.default_value);
+        for (&coord, &cell) in &self.cells {
+            grid[coord - min] = cell;
+        }
+        Some((grid, min))
+    }
+}
+
+impl<T: Copy + PartialEq> Index<Coord> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: Coord) -> &Self::Output {
+        self.cells.get(&index).unwrap_or(&self.default_value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_cells_are_not_stored() {
+        let mut grid = SparseGrid::new(0);
+        assert_eq!(grid[Coord::new(-5, 7)], 0);
+        assert_eq!(grid.bounds(), None);
+
+        grid.set(Coord::new(-2, 3), 4);
+        grid.set(Coord::new(1, -1), 2);
+        assert_eq!(grid[Coord::new(-2, 3)], 4);
+        assert_eq!(grid[Coord::new(9, 9)], 0);
+        assert_eq!(grid.bounds(), Some((Coord::new(-2, -1), Coord::new(1, 3))));
+
+        assert_eq!(grid.set(Coord::new(9, 9), 0), None);
+        assert_eq!(grid.set(Coord::new(1, -1), 0), Some(2));
+        assert!(!grid.is_set(Coord::new(1, -1)));
+        assert_eq!(grid.len(), 1);
+        assert_eq!(grid.bounds(), Some((Coord::new(-2, 3), Coord::new(-2, 3))));
+    }
+
+    #[test]
+    fn test_round_trip_through_grid() {
+        let grid = Grid::from("..#\n#..\n...");
+        let sparse = SparseGrid::from_grid(&grid, b'.');
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.position(b'#').map(|coord| sparse[coord]), Some(b'#'));
+        let (dense, origin) = sparse.to_grid().unwrap();
+        assert_eq!(origin, Coord::new(0, 0));
+        assert_eq!(dense.to_string(), "..#\n#..\n");
+    }
+}
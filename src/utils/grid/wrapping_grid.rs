@@ -0,0 +1,146 @@
+use super::{Coord, Grid};
+use std::ops::{Index, IndexMut};
+
+#[derive(Debug, Clone)]
+pub struct WrappingGrid<T: Copy + PartialEq> {
+    grid: Grid<T>,
+}
+
+impl<T: Copy + PartialEq> WrappingGrid<T> {
+    pub fn new(height: usize, width: usize, default_value: T) -> Self {
+        Self::from(Grid::new(height, width, default_value))
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    pub fn area(&self) -> usize {
+        self.grid.area()
+    }
+
+    pub fn wrap(&self, coord: Coord) -> Coord {
+        self.grid.wrap(coord)
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.grid.get(self.wrap(coord))
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        let coord = self.wrap(coord);
+        self.grid.get_mut(coord)
+    }
+
+    pub fn row(&self, r: isize) -> &[T] {
+        self.grid
+            .row(r.rem_euclid(self.grid.dimensions().r).unsigned_abs())
+    }
+
+    pub fn enumerate_cells(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.grid.enumerate_cells()
+    }
+
+    pub fn position(&self, target: T) -> Option<Coord> {
+        self.grid.position(target)
+    }
+
+    pub fn positions(&self, target: T) -> impl Iterator<Item = Coord> + use<'_, T> {
+        self.grid.positions(target)
+    }
+
+    pub fn fill(&mut self, value: T) {
+        self.grid.fill(value);
+    }
+
+    pub fn as_grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+}
+
+impl<T: Copy + PartialEq> Index<Coord> for WrappingGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: Coord) -> &Self::Output {
+        &self.grid[self.wrap(index)]
+    }
+}
+
+impl<T: Copy + PartialEq> IndexMut<Coord> for WrappingGrid<T> {
+    fn index_mut(&mut self, index: Coord) -> &mut Self::Output {
+        let index = self.wrap(index);
+        &mut self.grid[index]
+    }
+}
+
+impl<T: Copy + PartialEq> From<Grid<T>> for WrappingGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        assert!(
+            grid.height() > 0 && grid.width() > 0,
+            "Wrapping grid should have non-zero dimensions"
+        );
+        Self { grid }
+    }
+}
+
+impl From<&str> for WrappingGrid<u8> {
+    fn from(value: &str) -> Self {
+        Self::from(Grid::from(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indexing_wraps() {
+        let mut grid = WrappingGrid::from("abc\ndef");
+        assert_eq!(grid[Coord::new(0, 0)], b'a');
+        assert_eq!(grid[Coord::new(-1, -1)], b'f');
+        assert_eq!(grid[Coord::new(5, 7)], b'e');
+        assert_eq!(grid.wrap(Coord::new(-3, 10)), Coord::new(1, 1));
+        assert_eq!(grid.row(-2), b"abc");
+
+        grid[Coord::new(2, -3)] = b'x';
+        assert_eq!(grid.as_grid()[Coord::new(0, 0)], b'x');
+        assert_eq!(grid.get(Coord::new(-2, 3)), Some(&b'x'));
+    }
+
+    #[test]
+    fn test_far_out_of_range() {
+        let grid = WrappingGrid::from("abc\ndef");
+        for (coord, expected) in [
+            (Coord::new(-2, -3), Coord::new(0, 0)),
+            (Coord::new(-7, -8), Coord::new(1, 1)),
+            (Coord::new(1_000_001, -1_000_000), Coord::new(1, 2)),
+            (Coord::new(isize::MAX, isize::MAX), Coord::new(1, 1)),
+            (Coord::new(isize::MIN, isize::MIN), Coord::new(0, 1)),
+        ] {
+            assert_eq!(grid.wrap(coord), expected, "{coord:?}");
+            assert_eq!(grid[coord], grid.as_grid()[expected]);
+        }
+        assert_eq!(grid.row(-1_000_001), b"def");
+        assert!((-9..9).all(|r| (-9..9).all(|c| grid.get(Coord::new(r, c)).is_some())));
+    }
+
+    #[test]
+    #[should_panic(expected = "Wrapping grid should have non-zero dimensions")]
+    fn test_zero_dimensions() {
+        WrappingGrid::new(0, 3, false);
+    }
+
+    #[test]
+    #[should_panic(expected = "Wrapping grid should have non-zero dimensions")]
+    fn test_zero_width_grid() {
+        let _ = WrappingGrid::from(Grid::new(2, 0, b'.'));
+    }
+}
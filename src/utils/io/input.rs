@@ -1,12 +1,112 @@
-pub fn parse_args() -> Result<u8, String> {
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    Solve { day: u8, options: Options },
+    Replay { path: String, options: Options },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Options(Vec<(String, Option<String>)>);
+
+impl Options {
+    pub fn flag(&self, name: &str) -> bool {
+        self.0.iter().any(|(option, _)| option == name)
+    }
+
+    pub fn required(&self, name: &str) -> Result<Option<&str>, String> {
+        match self.0.iter().find(|(option, _)| option == name) {
+            Some((_, Some(value))) => Ok(Some(value)),
+            Some((_, None)) => Err(format!("Option --{name} requires a value")),
+            None => Ok(None),
+        }
+    }
+
+    pub fn reject_unknown(&self, known: &[&str]) -> Result<(), String> {
+        match self
+            .0
+            .iter()
+            .find(|(option, _)| !known.contains(&option.as_str()))
+        {
+            Some((option, _)) => Err(format!("Unknown option --{option}")),
+            None => Ok(()),
+        }
+    }
+
+    pub fn parsed<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.required(name)?
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("Invalid value for --{name}: {value}"))
+            })
+            .transpose()
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Vec::new();
+    let mut rest = args.iter().peekable();
+    while let Some(arg) = rest.next() {
+        let name = arg
+            .strip_prefix("--")
+            .ok_or_else(|| format!("Unexpected argument: {arg}"))?;
+        let value = rest.next_if(|next| !next.starts_with("--")).cloned();
+        options.push((name.to_owned(), value));
+    }
+    Ok(Options(options))
+}
+
+pub fn parse_args() -> Result<Command, String> {
     let args: Vec<String> = std::env::args().collect();
-    if let Some(arg) = args.get(1) {
-        match arg.parse::<u8>() {
-            Ok(num) if (1..=25).contains(&num) => Ok(num),
-            Ok(_) => Err("Day must be between 1 and 25".into()),
-            Err(_) => Err("Invalid number".into()),
-        }
-    } else {
-        Err(format!("Usage: {} <day>", args[0]))
+    match args.get(1).map(String::as_str) {
+        Some("replay") => {
+            let path = args
+                .get(2)
+                .ok_or_else(|| format!("Usage: {} replay <file> [--option value]...", args[0]))?;
+            Ok(Command::Replay {
+                path: path.clone(),
+                options: parse_options(&args[3..])?,
+            })
+        }
+        Some(arg) => {
+            let day = match arg.parse::<u8>() {
+                Ok(num) if (1..=25).contains(&num) => num,
+                Ok(_) => return Err("Day must be between 1 and 25".into()),
+                Err(_) => return Err("Invalid number".into()),
+            };
+            Ok(Command::Solve {
+                day,
+                options: parse_options(&args[2..])?,
+            })
+        }
+        None => Err(format!(
+            "Usage: {0} <day> [--option [value]]... | {0} replay <file>",
+            args[0]
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(args: &[&str]) -> Options {
+        parse_options(&args.iter().map(|&arg| arg.to_owned()).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn test_required_values() {
+        let options = options(&["--trace", "--record", "out.log", "--frames"]);
+        assert!(options.flag("trace"));
+        assert_eq!(options.required("record"), Ok(Some("out.log")));
+        assert_eq!(
+            options.required("frames"),
+            Err("Option --frames requires a value".to_owned())
+        );
+        assert_eq!(options.required("from"), Ok(None));
+        assert_eq!(options.parsed::<usize>("from"), Ok(None));
+        assert!(options.parsed::<usize>("frames").is_err());
+        assert!(options.parsed::<usize>("record").is_err());
     }
 }
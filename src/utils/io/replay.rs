@@ -0,0 +1,230 @@
+use crate::utils::grid::{Frame, Grid, Recording};
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+    thread,
+    time::Duration,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Playback {
+    pub speed: f64,
+    pub from: usize,
+    pub to: Option<usize>,
+}
+
+impl Playback {
+    pub fn new(speed: f64, from: usize, to: Option<usize>) -> Result<Self, String> {
+        if !(speed.is_finite() && speed > 0.0) {
+            return Err(format!(
+                "Playback speed should be positive and finite, got {speed}"
+            ));
+        }
+        Ok(Self { speed, from, to })
+    }
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            from: 0,
+            to: None,
+        }
+    }
+}
+
+pub fn replay(path: impl AsRef<Path>, playback: Playback) -> io::Result<()> {
+    let path = path.as_ref();
+    if path
+        .extension()
+        .is_some_and(|extension| extension == "cast")
+    {
+        replay_asciicast(path, playback)
+    } else {
+        replay_frame_log(path, playback)
+    }
+}
+
+fn replay_frame_log(path: &Path, playback: Playback) -> io::Result<()> {
+    let recording = Recording::load_frame_log(path)?;
+    if recording.is_empty() {
+        return Ok(());
+    }
+    let last = playback.to.unwrap_or(usize::MAX).min(recording.len() - 1);
+    let delay = recording.interval().div_f64(playback.speed);
+    let mut grid = recording
+        .snapshot(playback.from)
+        .expect("Recording should contain frames");
+    let mut stdout = io::stdout().lock();
+
+    draw_grid(&mut stdout, &grid)?;
+    for (i, frame) in recording.frames().iter().enumerate() {
+        if i <= playback.from {
+            continue;
+        }
+        if i > last {
+            break;
+        }
+        thread::sleep(delay);
+        frame.apply_to(&mut grid);
+        match frame {
+            Frame::Key(_) => draw_grid(&mut stdout, &grid)?,
+            Frame::Diff(cells) => {
+                for &(coord, cell) in cells {
+                    write!(
+                        stdout,
+                        "\x1b[{};{}H{}",
+                        coord.r + 1,
+                        coord.c + 1,
+                        cell as char
+                    )?;
+                }
+            }
+        }
+        write!(
+            stdout,
+            "\x1b[{};1H\x1b[Kframe {i}/{}",
+            grid.height() + 1,
+            recording.len() - 1
+        )?;
+        stdout.flush()?;
+    }
+    writeln!(stdout)
+}
+
+fn draw_grid<W: Write>(writer: &mut W, grid: &Grid<u8>) -> io::Result<()> {
+    write!(writer, "\x1b[2J\x1b[H{grid}")?;
+    writer.flush()
+}
+
+fn replay_asciicast(path: &Path, playback: Playback) -> io::Result<()> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_owned());
+    let contents = fs::read_to_string(path)?;
+    let mut lines = contents.lines();
+    lines
+        .next()
+        .filter(|header| header.contains(r#""version": 2"#))
+        .ok_or_else(|| invalid("Asciicast header should declare version 2"))?;
+
+    let events = lines
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_event(line).ok_or_else(|| invalid("Asciicast event is malformed")))
+        .collect::<io::Result<Vec<_>>>()?;
+    let last = playback.to.unwrap_or(usize::MAX);
+    let mut stdout = io::stdout().lock();
+    let mut previous_time = None;
+
+    for (i, (time, data)) in events.into_iter().enumerate() {
+        if i > last {
+            break;
+        }
+        if i > playback.from {
+            if let Some(previous_time) = previous_time {
+                let gap: f64 = time - previous_time;
+                thread::sleep(Duration::from_secs_f64(gap.max(0.0) / playback.speed));
+            }
+        }
+        previous_time = Some(time);
+        write!(stdout, "{data}")?;
+        stdout.flush()?;
+    }
+    writeln!(stdout)
+}
+
+fn parse_event(line: &str) -> Option<(f64, String)> {
+    let inner = line.trim().strip_prefix('[')?.strip_suffix(']')?;
+    let (time, rest) = inner.split_once(',')?;
+    let (kind, data) = rest.split_once(',')?;
+    if kind.trim() != r#""o""# {
+        return Some((time.trim().parse().ok()?, String::new()));
+    }
+    Some((time.trim().parse().ok()?, unescape_json(data.trim())?))
+}
+
+fn unescape_json(quoted: &str) -> Option<String> {
+    let mut chars = quoted.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut unescaped = String::new();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            unescaped.push(ch);
+            continue;
+        }
+        match chars.next()? {
+            'n' => unescaped.push('\n'),
+            'r' => unescaped.push('\r'),
+            't' => unescaped.push('\t'),
+            'b' => unescaped.push('\u{8}'),
+            'f' => unescaped.push('\u{c}'),
+            'u' => {
+                let hex: String = chars.by_ref().take(4).collect();
+                if hex.len() != 4 {
+                    return None;
+                }
+                unescaped.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+            }
+            escaped => unescaped.push(escaped),
+        }
+    }
+    Some(unescaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_playback_validation() {
+        assert!(Playback::new(2.0, 0, None).is_ok());
+        for speed in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(Playback::new(speed, 0, None).is_err(), "{speed}");
+        }
+    }
+
+    #[test]
+    fn test_unescape_json() {
+        assert_eq!(
+            unescape_json(r#""a\"b\\c\r\n\t\b\f\u001b[H""#).as_deref(),
+            Some("a\"b\\c\r\n\t\u{8}\u{c}\x1b[H")
+        );
+        assert_eq!(unescape_json(r#""é""#).as_deref(), Some("é"));
+        assert_eq!(unescape_json("unquoted"), None);
+        assert_eq!(unescape_json(r#""trailing\""#), None);
+        assert_eq!(unescape_json(r#""\u12""#), None);
+    }
+
+    #[test]
+    fn test_asciicast_escaping_round_trips() {
+        let mut recording = Recording::new(Duration::from_millis(500));
+        recording.capture(&Grid::from("\"\\\n.."));
+        let mut bytes = vec![];
+        recording.write_asciicast(&mut bytes).unwrap();
+        let cast = String::from_utf8(bytes).unwrap();
+        let mut lines = cast.lines();
+        assert_eq!(
+            lines.next(),
+            Some(r#"{"version": 2, "width": 2, "height": 3}"#)
+        );
+        assert_eq!(
+            parse_event(lines.next().unwrap()),
+            Some((0.0, "\x1b[2J\x1b[H\"\\\r\n..\r\n".to_owned()))
+        );
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_parse_event() {
+        assert_eq!(
+            parse_event(r#"[1.5, "o", "a, b"]"#),
+            Some((1.5, "a, b".to_owned()))
+        );
+        assert_eq!(
+            parse_event(r#" [0.25, "i", "x"] "#),
+            Some((0.25, String::new()))
+        );
+        assert_eq!(parse_event(r#"[x, "o", "a"]"#), None);
+        assert_eq!(parse_event(r#"[1.0, "o"]"#), None);
+        assert_eq!(parse_event(r#"1.0, "o", "a""#), None);
+    }
+}
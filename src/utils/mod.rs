@@ -1,3 +1,8 @@
 pub mod bench;
+pub mod cycle;
 pub mod grid;
 pub mod io;
+pub mod parse;
+#[cfg(test)]
+pub mod rng;
+pub mod scan;
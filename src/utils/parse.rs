@@ -0,0 +1,258 @@
+use std::{any, borrow::Cow, error::Error, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+pub fn normalise(input: &str) -> Cow<'_, str> {
+    if input.contains('\r') {
+        Cow::Owned(input.replace("\r\n", "\n").replace('\r', "\n"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    pub text: &'a str,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl<'a> Span<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        }
+    }
+
+    pub fn slice(&self, start: usize, end: usize) -> Self {
+        let skipped = &self.text[..start];
+        let (line, column) = match skipped.rfind('\n') {
+            Some(newline) => (
+                self.line + skipped.matches('\n').count(),
+                skipped[newline + 1..].chars().count() + 1,
+            ),
+            None => (self.line, self.column + skipped.chars().count()),
+        };
+        Self {
+            text: &self.text[start..end],
+            line,
+            column,
+        }
+    }
+
+    fn sub(&self, part: &'a str) -> Self {
+        let start = part.as_ptr() as usize - self.text.as_ptr() as usize;
+        self.slice(start, start + part.len())
+    }
+
+    pub fn trim(&self) -> Self {
+        self.sub(self.text.trim())
+    }
+
+    pub fn lines(self) -> impl Iterator<Item = Span<'a>> {
+        self.text.lines().enumerate().map(move |(i, line)| Span {
+            text: line,
+            line: self.line + i,
+            column: if i == 0 { self.column } else { 1 },
+        })
+    }
+
+    pub fn sections(self) -> impl Iterator<Item = Span<'a>> {
+        let mut line = self.line;
+        let mut column = self.column;
+        self.text.split("\n\n").filter_map(move |section| {
+            let span = Span {
+                text: section,
+                line,
+                column,
+            };
+            line += section.matches('\n').count() + 2;
+            column = 1;
+            let trimmed = section.trim_matches('\n');
+            (!trimmed.trim().is_empty()).then(|| span.sub(trimmed))
+        })
+    }
+
+    pub fn sections_array<const N: usize>(&self) -> ParseResult<[Span<'a>; N]> {
+        let sections: Vec<_> = self.sections().collect();
+        let found = sections.len();
+        sections
+            .try_into()
+            .map_err(|_| self.error(format!("expected {N} sections, found {found}")))
+    }
+
+    pub fn split<'s>(self, separator: &'s str) -> impl Iterator<Item = Span<'a>> + 's
+    where
+        'a: 's,
+    {
+        self.text.split(separator).map(move |part| self.sub(part))
+    }
+
+    pub fn split_once(&self, separator: &str) -> ParseResult<(Span<'a>, Span<'a>)> {
+        let (left, right) = self
+            .text
+            .split_once(separator)
+            .ok_or_else(|| self.error(format!("expected '{separator}' in '{}'", self.text)))?;
+        Ok((self.sub(left), self.sub(right)))
+    }
+
+    pub fn key_value(&self) -> ParseResult<(Span<'a>, Span<'a>)> {
+        let (key, value) = self.split_once(":")?;
+        Ok((key.trim(), value.trim()))
+    }
+
+    pub fn value_of(&self, key: &str) -> ParseResult<Span<'a>> {
+        let (found, value) = self.key_value()?;
+        if found.text == key {
+            Ok(value)
+        } else {
+            Err(found.error(format!("expected key '{key}', found '{}'", found.text)))
+        }
+    }
+
+    pub fn parse<T: FromStr>(&self) -> ParseResult<T> {
+        self.text.parse().map_err(|_| {
+            self.error(format!(
+                "invalid {}: '{}'",
+                any::type_name::<T>(),
+                self.text
+            ))
+        })
+    }
+
+    pub fn list<T, F>(&self, separator: &str, mut item: F) -> ParseResult<Vec<T>>
+    where
+        F: FnMut(Span<'a>) -> ParseResult<T>,
+    {
+        self.split(separator)
+            .map(|part| item(part.trim()))
+            .collect()
+    }
+
+    pub fn integers<T: FromStr>(self) -> impl Iterator<Item = ParseResult<T>> + 'a {
+        let bytes = self.text.as_bytes();
+        let (mut i, mut line, mut column) = (0, self.line, self.column);
+        std::iter::from_fn(move || {
+            while i < bytes.len() {
+                let signed = matches!(bytes[i], b'-' | b'+')
+                    && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+                if signed || bytes[i].is_ascii_digit() {
+                    let start = i;
+                    i += 1;
+                    while i < bytes.len() && bytes[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                    let number = Span {
+                        text: &self.text[start..i],
+                        line,
+                        column,
+                    };
+                    column += i - start;
+                    return Some(number.parse());
+                }
+                if bytes[i] == b'\n' {
+                    (line, column) = (line + 1, 1);
+                } else if bytes[i] & 0xC0 != 0x80 {
+                    column += 1;
+                }
+                i += 1;
+            }
+            None
+        })
+    }
+
+    pub fn integers_array<T: FromStr, const N: usize>(&self) -> ParseResult<[T; N]> {
+        let integers = self.integers().collect::<ParseResult<Vec<T>>>()?;
+        let found = integers.len();
+        integers
+            .try_into()
+            .map_err(|_| self.error(format!("expected {N} integers, found {found}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position<T>(result: ParseResult<T>) -> (usize, usize) {
+        let error = result.err().expect("Input should be rejected");
+        (error.line, error.column)
+    }
+
+    #[test]
+    fn test_integer_positions() {
+        let span = Span::new("p=1,2\n  v=300,-4");
+        assert_eq!(position(span.integers_array::<u8, 4>()), (2, 5));
+        assert_eq!(span.integers_array::<i16, 4>(), Ok([1, 2, 300, -4]));
+        assert_eq!(
+            position(Span::new("é=1 ü=-2").integers::<u8>().nth(1).unwrap()),
+            (1, 7)
+        );
+    }
+
+    #[test]
+    fn test_list_positions() {
+        let line = Span::new("header\n  12, 3x, 4").lines().nth(1).unwrap();
+        let error = line.list(",", |item| item.parse::<u32>()).unwrap_err();
+        assert_eq!(error.to_string(), "2:7: invalid u32: '3x'");
+    }
+
+    #[test]
+    fn test_section_positions() {
+        let input = normalise("Register A: 5\r\n\r\n\r\nRegister B: x\r\nRegister Q: 6");
+        let [first, second] = Span::new(&input).sections_array().unwrap();
+        assert_eq!((first.line, first.column), (1, 1));
+        assert_eq!((second.line, second.column), (4, 1));
+
+        let mut lines = second.lines();
+        let value = lines.next().unwrap().value_of("Register B").unwrap();
+        assert_eq!(position(value.parse::<u64>()), (4, 13));
+        let error = lines.next().unwrap().value_of("Register C").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "5:1: expected key 'Register C', found 'Register Q'"
+        );
+        assert_eq!(position(Span::new(&input).sections_array::<3>()), (1, 1));
+    }
+
+    #[test]
+    fn test_separator_positions() {
+        let line = Span::new("47|53\n97 13").lines().nth(1).unwrap();
+        assert_eq!(position(line.split_once("|")), (2, 1));
+        let (left, right) = Span::new("  47 | 53").split_once("|").unwrap();
+        assert_eq!((left.trim().column, right.trim().column), (3, 8));
+    }
+}
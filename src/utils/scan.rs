@@ -0,0 +1,331 @@
+use std::{error::Error, fmt, marker::PhantomData};
+
+pub trait Integer: Copy {
+    const SIGNED: bool;
+    const ZERO: Self;
+
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($signed:literal: $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const SIGNED: bool = $signed;
+                const ZERO: Self = 0;
+
+                fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                    let shifted = self.checked_mul(10)?;
+                    if negative {
+                        shifted.checked_sub(digit.into())
+                    } else {
+                        shifted.checked_add(digit.into())
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(false: u8, u16, u32, u64, u128, usize);
+impl_integer!(true: i16, i32, i64, i128, isize);
+
+const LANES: usize = 16;
+
+#[cfg(target_arch = "x86_64")]
+fn digit_mask(chunk: &[u8]) -> u32 {
+    use std::arch::x86_64::{
+        _mm_cmpeq_epi8, _mm_loadu_si128, _mm_min_epu8, _mm_movemask_epi8, _mm_set1_epi8,
+        _mm_sub_epi8,
+    };
+
+    assert!(chunk.len() >= LANES);
+    // SAFETY: SSE2 is part of the x86_64 baseline and the chunk holds at least 16 bytes
+    unsafe {
+        let bytes = _mm_loadu_si128(chunk.as_ptr().cast());
+        let offsets = _mm_sub_epi8(bytes, _mm_set1_epi8(b'0' as i8));
+        let is_digit = _mm_cmpeq_epi8(_mm_min_epu8(offsets, _mm_set1_epi8(9)), offsets);
+        _mm_movemask_epi8(is_digit) as u32
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn digit_mask(chunk: &[u8]) -> u32 {
+    scalar_digit_mask(chunk)
+}
+
+#[cfg(any(test, not(target_arch = "x86_64")))]
+fn scalar_digit_mask(chunk: &[u8]) -> u32 {
+    chunk[..LANES].iter().enumerate().fold(0, |mask, (i, b)| {
+        mask | (u32::from(b.is_ascii_digit()) << i)
+    })
+}
+
+fn digit_run(bytes: &[u8]) -> usize {
+    let mut offset = 0;
+    while bytes.len() - offset >= LANES {
+        let run = digit_mask(&bytes[offset..]).trailing_ones() as usize;
+        offset += run;
+        if run < LANES {
+            return offset;
+        }
+    }
+    offset
+        + bytes[offset..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanError {
+    Overflow {
+        offset: usize,
+    },
+    Expected {
+        offset: usize,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanError::Overflow { offset } => {
+                write!(f, "integer at byte {offset} does not fit the target type")
+            }
+            ScanError::Expected { offset, expected } => {
+                write!(f, "expected {expected} at byte {offset}")
+            }
+        }
+    }
+}
+
+impl Error for ScanError {}
+
+fn parse_digits<T: Integer>(digits: &[u8], negative: bool) -> Option<T> {
+    digits
+        .iter()
+        .try_fold(T::ZERO, |value, b| value.push_digit(b - b'0', negative))
+}
+
+pub struct Separated<'a, T> {
+    bytes: &'a [u8],
+    separator: &'a [u8],
+    pos: Option<usize>,
+    marker: PhantomData<T>,
+}
+
+impl<T: Integer> Iterator for Separated<'_, T> {
+    type Item = Result<T, ScanError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.pos.take()?;
+        let negative = T::SIGNED && self.bytes.get(start) == Some(&b'-');
+        let digits = start + usize::from(negative);
+        let end = digits + digit_run(&self.bytes[digits..]);
+        if end == digits {
+            return Some(Err(ScanError::Expected {
+                offset: digits,
+                expected: "integer",
+            }));
+        }
+        let Some(value) = parse_digits(&self.bytes[digits..end], negative) else {
+            return Some(Err(ScanError::Overflow { offset: start }));
+        };
+        if end < self.bytes.len() {
+            if !self.bytes[end..].starts_with(self.separator) {
+                return Some(Err(ScanError::Expected {
+                    offset: end,
+                    expected: "separator",
+                }));
+            }
+            self.pos = Some(end + self.separator.len());
+        }
+        Some(Ok(value))
+    }
+}
+
+pub fn separated<'a, T: Integer>(bytes: &'a [u8], separator: &'a [u8]) -> Separated<'a, T> {
+    assert!(
+        !separator.is_empty() && !separator.iter().any(u8::is_ascii_digit),
+        "Separator should be non-empty and contain no digits"
+    );
+    Separated {
+        bytes,
+        separator,
+        pos: Some(0),
+        marker: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{bench::time_execution, rng::Rng};
+
+    fn random_text(rng: &mut Rng, signed: bool) -> (String, &'static str, Vec<String>) {
+        let separators = [" ", "   ", ",", ": ", " v=", "\r\n"];
+        let separator = separators[rng.below(separators.len() as u64) as usize];
+        let tokens: Vec<String> = (0..=rng.below(64))
+            .map(|_| {
+                let mut token = String::new();
+                if signed && rng.below(3) == 0 {
+                    token.push('-');
+                }
+                let digits = match rng.below(4) {
+                    0 => rng.below(38) + 1,
+                    _ => rng.below(19) + 1,
+                };
+                for _ in 0..digits {
+                    token.push(char::from(b'0' + rng.below(10) as u8));
+                }
+                token
+            })
+            .collect();
+        (tokens.join(separator), separator, tokens)
+    }
+
+    fn scan_random<T>(seed: u64, signed: bool)
+    where
+        T: Integer + std::str::FromStr + PartialEq + fmt::Debug,
+        T::Err: fmt::Debug,
+    {
+        let mut rng = Rng::new(seed);
+        for _ in 0..2000 {
+            let (text, separator, tokens) = random_text(&mut rng, signed);
+            let expected: Vec<T> = tokens.iter().map(|t| t.parse().unwrap()).collect();
+            let scanned: Vec<T> = separated(text.as_bytes(), separator.as_bytes())
+                .map(Result::unwrap)
+                .collect();
+            assert_eq!(scanned, expected, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_unsigned_matches_str_parse() {
+        scan_random::<u128>(0x2024_1201, false);
+    }
+
+    #[test]
+    fn test_signed_matches_str_parse() {
+        scan_random::<i128>(0x0d0c_2024, true);
+    }
+
+    #[test]
+    fn test_digit_mask_matches_scalar() {
+        let bytes: Vec<u8> = (0..=255).chain((0..=255).rev()).collect();
+        for window in bytes.windows(LANES) {
+            assert_eq!(digit_mask(window), scalar_digit_mask(window));
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let text = format!("{} {}", i64::MIN, i64::MAX);
+        assert_eq!(
+            separated::<i64>(text.as_bytes(), b" ").collect::<Vec<_>>(),
+            vec![Ok(i64::MIN), Ok(i64::MAX)]
+        );
+        let text = u64::MAX.to_string();
+        assert_eq!(
+            separated::<u64>(text.as_bytes(), b" ").next(),
+            Some(Ok(u64::MAX))
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(
+            separated::<u8>(b"255 256 7", b" ").collect::<Vec<_>>(),
+            vec![Ok(255), Err(ScanError::Overflow { offset: 4 })]
+        );
+        assert_eq!(
+            separated::<i16>(b"1,-32769", b",").nth(1),
+            Some(Err(ScanError::Overflow { offset: 2 }))
+        );
+    }
+
+    #[test]
+    fn test_unsigned_rejects_minus() {
+        assert_eq!(
+            separated::<u32>(b"-1", b" ").next(),
+            Some(Err(ScanError::Expected {
+                offset: 0,
+                expected: "integer"
+            }))
+        );
+    }
+
+    #[test]
+    fn test_separated() {
+        let scan = |text: &str, separator: &str| {
+            separated::<i32>(text.as_bytes(), separator.as_bytes()).collect::<Result<Vec<_>, _>>()
+        };
+        assert_eq!(scan("3   4", "   "), Ok(vec![3, 4]));
+        assert_eq!(scan("7 -6 4", " "), Ok(vec![7, -6, 4]));
+        assert_eq!(
+            scan("3  4", "   "),
+            Err(ScanError::Expected {
+                offset: 1,
+                expected: "separator"
+            })
+        );
+        assert_eq!(
+            scan("1 2 x", " "),
+            Err(ScanError::Expected {
+                offset: 4,
+                expected: "integer"
+            })
+        );
+        assert_eq!(
+            scan("1 2 ", " "),
+            Err(ScanError::Expected {
+                offset: 4,
+                expected: "integer"
+            })
+        );
+        assert_eq!(
+            scan("", " "),
+            Err(ScanError::Expected {
+                offset: 0,
+                expected: "integer"
+            })
+        );
+        assert_eq!(
+            scan("1 99999999999", " "),
+            Err(ScanError::Overflow { offset: 2 })
+        );
+        assert_eq!(
+            ScanError::Overflow { offset: 2 }.to_string(),
+            "integer at byte 2 does not fit the target type"
+        );
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with --release -- --ignored"]
+    fn bench_against_str_parse() {
+        let mut rng = Rng::new(42);
+        let text = (0..200_000)
+            .map(|_| format!("{}   {}", rng.below(100_000), rng.below(100_000)))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let split = time_execution(|| {
+            text.lines()
+                .flat_map(|line| line.split_whitespace())
+                .map(|n| n.parse::<u64>().unwrap())
+                .sum::<u64>()
+        });
+        let scanned = time_execution(|| {
+            text.lines()
+                .flat_map(|line| separated::<u64>(line.as_bytes(), b"   "))
+                .map(Result::unwrap)
+                .sum::<u64>()
+        });
+
+        assert_eq!(split.result, scanned.result);
+        println!("str::parse: {:?}", split.duration);
+        println!("scan:       {:?}", scanned.duration);
+    }
+}